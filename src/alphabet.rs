@@ -5,6 +5,7 @@ pub enum Alphabet {
     Kanji,
     Symbol,
     Romaji,
+    Emoji,
     Other,
 }
 
@@ -61,6 +62,14 @@ impl Alphabet {
         matches!(self, Self::Romaji)
     }
 
+    /// Returns `true` if the alphabet is [`Emoji`].
+    ///
+    /// [`Emoji`]: Alphabet::Emoji
+    #[inline]
+    pub fn is_emoji(&self) -> bool {
+        matches!(self, Self::Emoji)
+    }
+
     /// Returns `true` if the alphabet is [`Other`].
     ///
     /// [`Other`]: Alphabet::Other
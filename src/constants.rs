@@ -2,3 +2,12 @@ use std::ops::Range;
 
 pub const WIDE_ALPHANUMERIC: Range<u32> = 0xff01..0xff5f;
 pub const NORMAL_ALPHANUMERIC: Range<u32> = 0x0021..0x007f;
+
+/// Fullwidth equivalents of the halfwidth katakana and punctuation block (U+FF61-FF9F), indexed by
+/// their offset to U+FF61.
+pub const HALFWIDTH_KATAKANA: &[char] = &[
+    '。', '「', '」', '、', '・', 'ヲ', 'ァ', 'ィ', 'ゥ', 'ェ', 'ォ', 'ャ', 'ュ', 'ョ', 'ッ', 'ー',
+    'ア', 'イ', 'ウ', 'エ', 'オ', 'カ', 'キ', 'ク', 'ケ', 'コ', 'サ', 'シ', 'ス', 'セ', 'ソ', 'タ',
+    'チ', 'ツ', 'テ', 'ト', 'ナ', 'ニ', 'ヌ', 'ネ', 'ノ', 'ハ', 'ヒ', 'フ', 'ヘ', 'ホ', 'マ', 'ミ',
+    'ム', 'メ', 'モ', 'ヤ', 'ユ', 'ヨ', 'ラ', 'リ', 'ル', 'レ', 'ロ', 'ワ', 'ン', '゛', '゜',
+];
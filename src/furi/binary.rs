@@ -0,0 +1,152 @@
+use super::{
+    segment::{
+        encode::FuriEncoder,
+        kanji::{as_kanji::AsKanjiSegment, KanjiRef},
+        AsSegment,
+    },
+    Furigana,
+};
+
+const TAG_KANA: u8 = 0;
+const TAG_KANJI: u8 = 1;
+
+impl<T> Furigana<T>
+where
+    T: AsRef<str>,
+{
+    /// Encodes the furigana segments into a compact binary representation which can be decoded
+    /// again using [`Furigana::from_bytes`]. Each segment starts with a tag byte followed by
+    /// length-prefixed strings: the text for kana segments and the literals, amount of readings
+    /// and all readings for kanji segments. Lengths are encoded as little endian `u32`. Returns an
+    /// error if a length doesn't fit into a `u32`.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, ()> {
+        let mut out = Vec::with_capacity(self.raw().len());
+
+        for seg in self.segments() {
+            if let Some(kanji) = seg.as_kanji() {
+                out.push(TAG_KANJI);
+                write_str(&mut out, kanji.literals())?;
+                write_len(&mut out, kanji.reading_count())?;
+                for reading in kanji.readings() {
+                    write_str(&mut out, reading)?;
+                }
+            } else {
+                out.push(TAG_KANA);
+                write_str(&mut out, seg.main_reading())?;
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+impl Furigana<String> {
+    /// Decodes furigana from its binary representation created by [`Furigana::to_bytes`]. Returns
+    /// an error if `bytes` is not a valid encoding.
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, ()> {
+        let mut out = String::with_capacity(bytes.len());
+        let mut enc = FuriEncoder::new(&mut out);
+        let mut readings = vec![];
+
+        while let Some((tag, rest)) = bytes.split_first() {
+            bytes = rest;
+
+            match *tag {
+                TAG_KANA => enc.write_kana(read_str(&mut bytes)?),
+                TAG_KANJI => {
+                    let lits = read_str(&mut bytes)?;
+                    let count = read_len(&mut bytes)?;
+                    readings.clear();
+                    for _ in 0..count {
+                        readings.push(read_str(&mut bytes)?);
+                    }
+                    let kanji = KanjiRef::new(lits, &readings);
+                    if !is_valid_block(&kanji) {
+                        return Err(());
+                    }
+                    enc.write_kanji(kanji);
+                }
+                _ => return Err(()),
+            }
+        }
+
+        Ok(Furigana(out))
+    }
+}
+
+/// Returns `true` if the decoded kanji block can be encoded without producing corrupt furigana.
+fn is_valid_block(kanji: &KanjiRef) -> bool {
+    let has_delimiter = |s: &str| s.contains(['[', '|', ']']);
+    !kanji.literals().is_empty()
+        && kanji.is_valid()
+        && !has_delimiter(kanji.literals())
+        && !kanji.readings().iter().any(|r| has_delimiter(r))
+}
+
+fn write_len(out: &mut Vec<u8>, len: usize) -> Result<(), ()> {
+    let len = u32::try_from(len).map_err(|_| ())?;
+    out.extend_from_slice(&len.to_le_bytes());
+    Ok(())
+}
+
+fn write_str(out: &mut Vec<u8>, s: &str) -> Result<(), ()> {
+    write_len(out, s.len())?;
+    out.extend_from_slice(s.as_bytes());
+    Ok(())
+}
+
+fn read_len(bytes: &mut &[u8]) -> Result<usize, ()> {
+    let (len, rest) = bytes.split_first_chunk::<4>().ok_or(())?;
+    *bytes = rest;
+    Ok(u32::from_le_bytes(*len) as usize)
+}
+
+fn read_str<'a>(bytes: &mut &'a [u8]) -> Result<&'a str, ()> {
+    let len = read_len(bytes)?;
+    if bytes.len() < len {
+        return Err(());
+    }
+    let (s, rest) = bytes.split_at(len);
+    *bytes = rest;
+    std::str::from_utf8(s).map_err(|_| ())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("[音楽|おん|がく]が[好|す]きです"; "detailed")]
+    #[test_case("[音楽|おんがく]が[大好|だいす]き"; "merged")]
+    #[test_case("[2|][x|えっくす]+[1|]の[定義|てい|ぎ]"; "empty reading")]
+    #[test_case("おんがく"; "kana only")]
+    #[test_case(""; "empty")]
+    fn test_round_trip(furi: &str) {
+        let furi = Furigana(furi);
+        let bytes = furi.to_bytes().unwrap();
+        assert_eq!(Furigana::from_bytes(&bytes), Ok(furi.as_owned()));
+    }
+
+    #[test]
+    fn test_from_bytes_invalid() {
+        let bytes = Furigana("[音楽|おん|がく]が").to_bytes().unwrap();
+        assert!(Furigana::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(Furigana::from_bytes(&[2]).is_err());
+        assert!(Furigana::from_bytes(&[TAG_KANA, 2, 0, 0, 0, 0xff, 0xff]).is_err());
+    }
+
+    #[test_case("", &["おん"]; "empty literal")]
+    #[test_case("音楽", &[]; "no readings")]
+    #[test_case("音楽", &["お", "ん", "がく"]; "reading mismatch")]
+    #[test_case("音楽", &["おん|がく"]; "reading delimiter")]
+    #[test_case("音]楽", &["おんがく"]; "literal delimiter")]
+    fn test_from_bytes_invalid_block(lits: &str, readings: &[&str]) {
+        let mut bytes = vec![TAG_KANJI];
+        write_str(&mut bytes, lits).unwrap();
+        write_len(&mut bytes, readings.len()).unwrap();
+        for reading in readings {
+            write_str(&mut bytes, reading).unwrap();
+        }
+        assert_eq!(Furigana::from_bytes(&bytes), Err(()));
+    }
+}
@@ -1,8 +1,8 @@
-use crate::furi::segment::kanji::as_kanji::AsKanjiSegment;
+use crate::{furi::segment::kanji::as_kanji::AsKanjiSegment, JapaneseExt};
 
 use super::{
     parse::unchecked::UncheckedFuriParser,
-    segment::{encode::FuriEncoder, AsSegment},
+    segment::{encode::FuriEncoder, kanji::KanjiRef, AsSegment},
     Furigana,
 };
 use std::mem::swap;
@@ -71,7 +71,11 @@ where
 
             let seg = UncheckedFuriParser::from_seg_str(sub, true);
 
-            let kanji = seg.as_kanji().unwrap();
+            // Reading-only blocks are parsed as kana.
+            let Some(kanji) = seg.as_kanji() else {
+                enc.write_seg(&seg);
+                continue;
+            };
             let readings = kanji.readings();
 
             if readings.is_empty() {
@@ -95,6 +99,132 @@ where
         self
     }
 
+    /// Converts kanji blocks whose reading is equal to their literals to kana.
+    /// eg. [カタカナ|カタカナ] => カタカナ
+    pub fn drop_redundant_readings(mut self) -> Self {
+        let (str, buf) = self.get_src();
+        let mut enc = FuriEncoder::new(buf);
+
+        for (sub, is_kanji) in Furigana(str).gen_parser() {
+            if !is_kanji {
+                enc.write_kana(sub);
+                continue;
+            }
+
+            let seg = UncheckedFuriParser::from_seg_str(sub, is_kanji);
+            if seg
+                .as_kanji()
+                .is_some_and(|k| !k.is_empty() && seg.matches_kana(k.literals()))
+            {
+                enc.write_kana(seg.main_reading());
+                continue;
+            }
+
+            enc.write_kana(sub);
+        }
+
+        self
+    }
+
+    /// Converts the readings of all kanji blocks to hiragana. Kana segments are kept as they are.
+    /// eg. [音楽|オン|ガク]の[CD|シーディー] => [音楽|おん|がく]の[CD|しーでぃー]
+    pub fn readings_to_hiragana(mut self) -> Self {
+        let (str, buf) = self.get_src();
+        let mut enc = FuriEncoder::new(buf);
+
+        for (sub, is_kanji) in Furigana(str).gen_parser() {
+            if !is_kanji {
+                enc.write_kana(sub);
+                continue;
+            }
+
+            let seg = UncheckedFuriParser::from_seg_str(sub, true);
+
+            // Reading-only blocks are parsed as kana.
+            let Some(kanji) = seg.as_kanji() else {
+                enc.write_kana(sub);
+                continue;
+            };
+
+            // Convert the raw readings to keep the block structure as it is.
+            let (lits, readings) = sub.split_at(kanji.literals().len() + 1);
+            enc.write_kana(lits);
+            enc.write_kana(&readings.to_hiragana());
+        }
+
+        self
+    }
+
+    /// Converts halfwidth katakana in readings and text to fullwidth katakana.
+    /// eg. [音楽|ｵﾝｶﾞｸ]の[CD|ｼｰﾃﾞｨｰ] => [音楽|オンガク]の[CD|シーディー]
+    pub fn normalize_width(mut self) -> Self {
+        let (str, buf) = self.get_src();
+
+        // Halfwidth katakana never contains the furigana control characters `[`, `|` and `]`
+        // so the whole string can be converted at once.
+        buf.push_str(&str.to_fullwidth_kana());
+
+        self
+    }
+
+    /// Splits single readings of kanji blocks with multiple literals evenly across all literals.
+    /// This is the inverse of `fix_kanji_blocks`. The distribution is only a heuristic based on
+    /// the amount of kana and the resulting readings may not be the actual readings of each
+    /// literal. Small kana are never separated from the kana before them and blocks with less kana
+    /// than literals are kept as they are.
+    /// eg. [音楽|おんがく] => [音楽|おん|がく]
+    pub fn distribute_readings(mut self) -> Self {
+        let (str, buf) = self.get_src();
+        let mut enc = FuriEncoder::new(buf);
+
+        for (sub, is_kanji) in Furigana(str).gen_parser() {
+            if !is_kanji {
+                enc.write_kana(sub);
+                continue;
+            }
+
+            let seg = UncheckedFuriParser::from_seg_str(sub, true);
+            let Some(kanji) = seg.as_kanji() else {
+                enc.write_kana(sub);
+                continue;
+            };
+
+            let lit_count = kanji.lit_count();
+            let readings = kanji.readings();
+            if lit_count < 2 || readings.len() != 1 {
+                enc.write_kana(sub);
+                continue;
+            }
+
+            let reading = readings[0];
+            let mora_starts: Vec<usize> = reading
+                .char_indices()
+                .filter(|(pos, c)| *pos == 0 || !c.is_small_kana())
+                .map(|(pos, _)| pos)
+                .collect();
+            if mora_starts.len() < lit_count {
+                enc.write_kana(sub);
+                continue;
+            }
+
+            // The first literals get one mora more if they can't be distributed evenly.
+            let per_lit = mora_starts.len() / lit_count;
+            let extra = mora_starts.len() % lit_count;
+            let mut split_readings = Vec::with_capacity(lit_count);
+            let mut mora = 0;
+            for i in 0..lit_count {
+                let start = mora_starts[mora];
+                mora += per_lit + usize::from(i < extra);
+                let end = mora_starts.get(mora).copied().unwrap_or(reading.len());
+                split_readings.push(&reading[start..end]);
+            }
+
+            enc.write_kanji(KanjiRef::new(kanji.literals(), &split_readings));
+        }
+
+        self
+    }
+
     /// Converts kanji blocks without readings to kana.
     pub fn remove_empty_kanji(mut self) -> Self {
         let (str, buf) = self.get_src();
@@ -107,7 +237,11 @@ where
             }
             let seg = UncheckedFuriParser::from_seg_str(sub, is_kanji);
 
-            let kanji = seg.as_kanji().unwrap();
+            // Reading-only blocks are parsed as kana.
+            let Some(kanji) = seg.as_kanji() else {
+                enc.write_seg(&seg);
+                continue;
+            };
             let readings = kanji.readings();
             if readings.len() == 1 && readings[0].is_empty() {
                 enc.write_kana(kanji.literals());
@@ -313,12 +447,64 @@ mod test {
         "[永遠|えい|えん]にあなたのものです。 [アーメン]",
         "[永遠|えい|えん]にあなたのものです。 [アーメン]"; "brackets"
     )]
+    #[test_case("[|おん]がく", "おんがく"; "reading only")]
     fn test_remove_empty_kanji(s: &str, exp: &str) {
         let furi = Furigana(s);
         let out = CodeFormatter::new(&furi).remove_empty_kanji().finish();
         assert_eq!(out, exp);
     }
 
+    #[test_case("[カタカナ|カタカナ]です", "カタカナです"; "single")]
+    #[test_case("[ab|a|b][音楽|おん|がく]", "ab[音楽|おん|がく]"; "detailed")]
+    #[test_case("[6|][時|じ]", "[6|][時|じ]"; "empty reading")]
+    #[test_case("[音|おと][楽|楽]", "[音|おと]楽"; "kanji reading")]
+    #[test_case(
+        "[永遠|えい|えん]にあなたのものです。 [アーメン]",
+        "[永遠|えい|えん]にあなたのものです。 [アーメン]"; "brackets"
+    )]
+    fn test_drop_redundant_readings(s: &str, exp: &str) {
+        let furi = Furigana(s);
+        let out = CodeFormatter::new(&furi).drop_redundant_readings().finish();
+        assert_eq!(out, exp);
+        assert_eq!(out.kanji_str(), furi.kanji_str());
+    }
+
+    #[test_case("[音楽|オン|ガク]", "[音楽|おん|がく]"; "detailed")]
+    #[test_case("[音楽|オンガク]が[好|ス]き", "[音楽|おんがく]が[好|す]き"; "merged")]
+    #[test_case("カタカナの[CD|シーディー]", "カタカナの[CD|しーでぃー]"; "kana untouched")]
+    #[test_case("[6|][時|ジ]", "[6|][時|じ]"; "empty reading")]
+    #[test_case("[音楽大|オンガク|ダイ]", "[音楽大|おんがく|だい]"; "invalid block")]
+    #[test_case("[|オン]ガク", "[|オン]ガク"; "reading only")]
+    fn test_readings_to_hiragana(s: &str, exp: &str) {
+        let furi = Furigana(s);
+        let out = CodeFormatter::new(&furi).readings_to_hiragana().finish();
+        assert_eq!(out, exp);
+        assert_eq!(out.kanji_str(), furi.kanji_str());
+    }
+
+    #[test_case("[音楽|おんがく]", "[音楽|おん|がく]"; "even")]
+    #[test_case("[大丈夫|だいじょうぶ]", "[大丈夫|だい|じょう|ぶ]"; "uneven")]
+    #[test_case("[今日|きょう]が[好|す]き", "[今日|きょ|う]が[好|す]き"; "small kana")]
+    #[test_case("[音楽|おん|がく]", "[音楽|おん|がく]"; "detailed")]
+    #[test_case("[三日月|みか]", "[三日月|みか]"; "too short")]
+    #[test_case("[音楽|ゃあい]", "[音楽|ゃあ|い]"; "leading small kana")]
+    #[test_case("[2|][x|えっくす]", "[2|][x|えっくす]"; "single literal")]
+    fn test_distribute_readings(s: &str, exp: &str) {
+        let furi = Furigana(s);
+        let out = CodeFormatter::new(&furi).distribute_readings().finish();
+        assert_eq!(out, exp);
+        assert_eq!(out.kana_str(), furi.kana_str());
+    }
+
+    #[test_case("[音楽|ｵﾝ|ｶﾞｸ]", "[音楽|オン|ガク]"; "detailed")]
+    #[test_case("ｶﾀｶﾅの[CD|ｼｰﾃﾞｨｰ]", "カタカナの[CD|シーディー]"; "kana")]
+    #[test_case("[音楽|おんがく]が[好|す]き", "[音楽|おんがく]が[好|す]き"; "unchanged")]
+    fn test_normalize_width(s: &str, exp: &str) {
+        let furi = Furigana(s);
+        let out = CodeFormatter::new(&furi).normalize_width().finish();
+        assert_eq!(out, exp);
+    }
+
     #[test_case("[音楽大|おんがく|だい]", "[音楽大|おんがくだい]"; "1")]
     #[test_case("おんがくが[好|す]","おんがくが[好|す]"; "End_kanji")]
     #[test_case("おんがくが[好|す]きです", "おんがくが[好|す]きです")]
@@ -332,6 +518,7 @@ mod test {
         "[永遠|えい|えん]にあなたのものです。 [アーメン]",
         "[永遠|えい|えん]にあなたのものです。 [アーメン]"; "brackets"
     )]
+    #[test_case("[|おん]がく", "おんがく"; "reading only")]
     fn test_fix_kanji_blocks(s: &str, exp: &str) {
         let furi = Furigana(s);
         let out = CodeFormatter::new(&furi).fix_kanji_blocks().finish();
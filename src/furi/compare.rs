@@ -43,6 +43,20 @@ impl FuriComparator {
         }
     }
 
+    /// Returns a similarity score in the range `0.0..=1.0` of two segments based on the longest
+    /// common subsequence of their kana readings. Equal readings score `1.0`, readings without any
+    /// common character score `0.0`. If `lit_match` is set, the kanji literals are taken into
+    /// account as well.
+    pub fn similarity<L: AsSegment, R: AsSegment>(&self, left: &L, right: &R) -> f32 {
+        let reading_sim = lcs_ratio(&left.get_kana_reading(), &right.get_kana_reading());
+        if !self.lit_match {
+            return reading_sim;
+        }
+
+        let lit_sim = lcs_ratio(left.main_reading().as_ref(), right.main_reading().as_ref());
+        (reading_sim + lit_sim) / 2.0
+    }
+
     #[inline]
     fn eq_seq_no_lit_match<L: AsSegment, R: AsSegment>(
         &self,
@@ -74,10 +88,39 @@ impl FuriComparator {
     }
 }
 
+/// Returns the length of the longest common subsequence of `a` and `b` relative to their average
+/// length.
+fn lcs_ratio(a: &str, b: &str) -> f32 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+
+    // Only keep the previous row of the LCS table.
+    let mut prev = vec![0usize; b.len() + 1];
+    let mut cur = vec![0usize; b.len() + 1];
+    for ac in &a {
+        for (j, bc) in b.iter().enumerate() {
+            cur[j + 1] = if ac == bc {
+                prev[j] + 1
+            } else {
+                cur[j].max(prev[j + 1])
+            };
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+
+    (2 * prev[b.len()]) as f32 / (a.len() + b.len()) as f32
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::furi::seq::FuriSequence;
+    use crate::furi::{
+        segment::{s_ref::SegmentRef, Segment},
+        seq::FuriSequence,
+    };
     use std::str::FromStr;
     use test_case::test_case;
 
@@ -96,4 +139,27 @@ mod test {
         let b = FuriSequence::from_str(b).unwrap();
         assert!(!FuriComparator::new(lit_match).eq_seq(&a, &b));
     }
+
+    #[test_case("[音楽|おん|がく]", "[音楽|おん|がく]", 1.0)]
+    #[test_case("[音楽|おんがく]", "[音楽|おん|がく]", 1.0)]
+    #[test_case("かな", "かな", 1.0)]
+    #[test_case("[日本|にほん]", "[日本|にっぽん]", 4.0 / 7.0)]
+    #[test_case("[音|おと]", "[音|いん]", 0.0)]
+    fn test_similarity(a: &str, b: &str, exp: f32) {
+        let a: Segment = SegmentRef::from_str_unchecked(a).to_owned();
+        let b: Segment = SegmentRef::from_str_unchecked(b).to_owned();
+        let sim = FuriComparator::new(false).similarity(&a, &b);
+        assert!((sim - exp).abs() < f32::EPSILON, "{sim} != {exp}");
+    }
+
+    #[test]
+    fn test_similarity_lit_match() {
+        let a = SegmentRef::from_str_unchecked("[日本|にほん]");
+        let b = SegmentRef::from_str_unchecked("[二本|にほん]");
+        assert_eq!(FuriComparator::new(false).similarity(&a, &b), 1.0);
+        assert_eq!(FuriComparator::new(true).similarity(&a, &b), 0.75);
+
+        let sim = FuriComparator::new(false).similarity(&a, &SegmentRef::new_kana("にほんご"));
+        assert!(sim > 0.0 && sim < 1.0);
+    }
 }
@@ -0,0 +1,103 @@
+use super::Furigana;
+use std::collections::HashMap;
+
+/// Counts how often each `(kanji, reading)` pair of kanji blocks occurs across multiple furigana
+/// values.
+#[derive(Clone, Debug, Default)]
+pub struct ReadingCounter {
+    /// Counts of each reading grouped by kanji.
+    counts: HashMap<String, HashMap<String, usize>>,
+}
+
+impl ReadingCounter {
+    /// Create a new empty reading counter.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts all kanji blocks of `furi`.
+    pub fn add<T: AsRef<str>>(&mut self, furi: &Furigana<T>) {
+        for (kanji, reading) in furi.kanji_readings() {
+            let readings = match self.counts.get_mut(kanji) {
+                Some(readings) => readings,
+                None => self.counts.entry(kanji.to_string()).or_default(),
+            };
+            *readings.entry(reading).or_default() += 1;
+        }
+    }
+
+    /// Returns how often `kanji` has been read as `reading`.
+    #[inline]
+    pub fn count(&self, kanji: &str, reading: &str) -> usize {
+        self.counts
+            .get(kanji)
+            .and_then(|readings| readings.get(reading))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Returns the `n` most frequent `(kanji, reading)` pairs with their count. Pairs with an
+    /// equal count are ordered by kanji and reading.
+    pub fn top_n(&self, n: usize) -> Vec<((&str, &str), usize)> {
+        let mut out: Vec<_> = self
+            .counts
+            .iter()
+            .flat_map(|(kanji, readings)| {
+                readings
+                    .iter()
+                    .map(move |(reading, count)| ((kanji.as_str(), reading.as_str()), *count))
+            })
+            .collect();
+        out.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        out.truncate(n);
+        out
+    }
+
+    /// Returns the amount of distinct `(kanji, reading)` pairs.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.counts.values().map(|readings| readings.len()).sum()
+    }
+
+    /// Returns `true` if no kanji block has been counted yet.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_reading_counter() {
+        let mut counter = ReadingCounter::new();
+        assert!(counter.is_empty());
+
+        for furi in [
+            "[音楽|おん|がく]が[好|す]きです",
+            "[音楽|おんがく]を[聞|き]く",
+            "[好|この]み",
+            "[好|す]きな[人|ひと]",
+        ] {
+            counter.add(&Furigana(furi));
+        }
+
+        assert_eq!(counter.len(), 5);
+        assert_eq!(counter.count("音楽", "おんがく"), 2);
+        assert_eq!(counter.count("好", "す"), 2);
+        assert_eq!(counter.count("好", "この"), 1);
+        assert_eq!(counter.count("好", "すき"), 0);
+        assert_eq!(
+            counter.top_n(3),
+            vec![
+                (("好", "す"), 2),
+                (("音楽", "おんがく"), 2),
+                (("人", "ひと"), 1)
+            ]
+        );
+        assert_eq!(counter.top_n(10).len(), 5);
+    }
+}
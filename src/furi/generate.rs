@@ -0,0 +1,138 @@
+use super::{segment::encode::FuriEncoder, Furigana};
+use crate::{tokenize::by_alphabet, JapaneseExt};
+use std::collections::HashMap;
+
+/// Generates furigana for a sentence `text` by aligning it with its kana `reading`. Kana and
+/// other non-kanji parts of `text` have to appear in `reading` as they are and the remaining
+/// reading is assigned to the kanji between them. Returns `None` if `text` and `reading` can't be
+/// aligned.
+///
+/// The alignment is a best-effort result as it can be ambiguous, eg. `音楽が好き` could be aligned
+/// as `[音楽|おん]が[好|くがす]き` as well. In such cases the alignment in which each kanji gets
+/// roughly two kana assigned is preferred.
+pub fn align_sentence(text: &str, reading: &str) -> Option<Furigana<String>> {
+    let runs: Vec<&str> = by_alphabet(text, true).collect();
+
+    let mut memo = HashMap::new();
+    align_runs(&runs, 0, reading, 0, &mut memo)?;
+
+    let mut out = String::with_capacity(text.len() + reading.len());
+    let mut encoder = FuriEncoder::new(&mut out);
+    let mut offset = 0;
+    for (pos, run) in runs.iter().enumerate() {
+        // All states on the best alignment have been memoized by `align_runs`.
+        let (_, end) = memo.get(&(pos, offset)).copied().flatten()?;
+        if run.is_kanji() {
+            encoder.write_block(run, &reading[offset..end]);
+        } else {
+            encoder.write_kana(run);
+        }
+        offset = end;
+    }
+
+    Some(Furigana(out))
+}
+
+/// Returns the lowest cost of aligning `runs[pos..]` with `reading[offset..]` or `None` if they
+/// can't be aligned. The lowest cost and the end of the reading assigned to `runs[pos]` are
+/// memoized for each `(pos, offset)` so each state is only computed once.
+fn align_runs(
+    runs: &[&str],
+    pos: usize,
+    reading: &str,
+    offset: usize,
+    memo: &mut HashMap<(usize, usize), Option<(usize, usize)>>,
+) -> Option<usize> {
+    let Some(run) = runs.get(pos) else {
+        return (offset == reading.len()).then_some(0);
+    };
+
+    if let Some(best) = memo.get(&(pos, offset)) {
+        return best.map(|i| i.0);
+    }
+
+    let rest = &reading[offset..];
+    let best = if !run.is_kanji() {
+        let end = offset + run.len();
+        if rest.starts_with(run) {
+            align_runs(runs, pos + 1, reading, end, memo).map(|cost| (cost, end))
+        } else {
+            None
+        }
+    } else {
+        let ends = rest
+            .char_indices()
+            .skip(1)
+            .map(|i| offset + i.0)
+            .chain((!rest.is_empty()).then_some(reading.len()));
+
+        let mut best: Option<(usize, usize)> = None;
+        for end in ends {
+            // Skip readings that can't be followed by the next run.
+            let next_fits = match runs.get(pos + 1) {
+                Some(next) => reading[end..].starts_with(next),
+                None => end == reading.len(),
+            };
+            if !next_fits {
+                continue;
+            }
+
+            let Some(rest_cost) = align_runs(runs, pos + 1, reading, end, memo) else {
+                continue;
+            };
+
+            let cost = rest_cost + kanji_cost(run, &reading[offset..end]);
+            if !matches!(best, Some((best_cost, _)) if best_cost <= cost) {
+                best = Some((cost, end));
+            }
+        }
+        best
+    };
+
+    memo.insert((pos, offset), best);
+    best.map(|i| i.0)
+}
+
+/// Returns how much the reading of a kanji run deviates from two kana per kanji.
+fn kanji_cost(kanji: &str, reading: &str) -> usize {
+    let diff = reading.real_len().abs_diff(kanji.real_len() * 2);
+    diff * diff
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("音楽が好き", "おんがくがすき", "[音楽|おんがく]が[好|す]き")]
+    #[test_case(
+        "日本語を勉強する",
+        "にほんごをべんきょうする",
+        "[日本語|にほんご]を[勉強|べんきょう]する"
+    )]
+    #[test_case("東京", "とうきょう", "[東京|とうきょう]"; "kanji only")]
+    #[test_case("かな", "かな", "かな"; "kana only")]
+    #[test_case("", "", ""; "empty")]
+    fn test_align_sentence(text: &str, reading: &str, exp: &str) {
+        assert_eq!(
+            align_sentence(text, reading),
+            Some(Furigana(exp.to_string()))
+        );
+    }
+
+    #[test_case("音楽が好き", "おんがくがすく"; "kana mismatch")]
+    #[test_case("音楽が好き", "がすき"; "missing kanji reading")]
+    #[test_case("かな", "かなかな"; "remaining reading")]
+    fn test_align_sentence_fail(text: &str, reading: &str) {
+        assert_eq!(align_sentence(text, reading), None);
+    }
+
+    #[test]
+    fn test_align_sentence_long() {
+        // Exhaustively trying all alignments of this takes exponential time.
+        let text = "日の本の国の".repeat(30);
+        let reading = "ひのもとのくにの".repeat(30);
+        let exp = "[日|ひ]の[本|もと]の[国|くに]の".repeat(30);
+        assert_eq!(align_sentence(&text, &reading), Some(Furigana(exp)));
+    }
+}
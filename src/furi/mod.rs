@@ -1,22 +1,38 @@
+#[cfg(feature = "binary")]
+pub mod binary;
 pub mod cformat;
 pub mod compare;
+pub mod freq;
+pub mod generate;
 pub mod parse;
 pub mod segment;
 pub mod seq;
 
-use crate::reading::{traits::AsReadingRef, Reading};
+use crate::{
+    reading::{traits::AsReadingRef, Reading, ReadingRef},
+    JapaneseExt,
+};
 use parse::{
-    reading::FuriToReadingParser, unchecked::UncheckedFuriParser, FuriParser, FuriParserGen,
+    reading::FuriToReadingParser, unchecked::UncheckedFuriParser, FuriParseError, FuriParser,
+    FuriParserGen,
+};
+use segment::{
+    kanji::{as_kanji::AsKanjiSegment, KanjiRef},
+    AsSegment, Segment, SegmentRef,
 };
-use segment::{AsSegment, Segment, SegmentRef};
 use std::{
-    borrow::Borrow,
+    borrow::{Borrow, Cow},
+    collections::HashMap,
     fmt::Display,
+    iter,
     ops::{Deref, Range},
 };
 
 use self::{cformat::CodeFormatter, segment::encode::FuriEncoder, seq::FuriSequence};
 
+/// Punctuation marking the end of a sentence.
+const SENTENCE_ENDS: [char; 3] = ['。', '！', '？'];
+
 /// A struct that holds encoded furigana data in a string. Such an element can be created by directly wrapping around
 /// a [`String`] or using the `new()` function which has the benefit that the furigana gets validated.
 /// Valid encoded furigana looks like this: `[拝金主義|はい|きん|しゅ|ぎ]は[問題|もん|だい]です。`
@@ -24,6 +40,19 @@ use self::{cformat::CodeFormatter, segment::encode::FuriEncoder, seq::FuriSequen
 #[cfg_attr(feature = "with_serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Furigana<T>(pub T);
 
+/// The way kanji blocks of a furigana value assign their readings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ReadingStyle {
+    /// All kanji blocks have a reading for each literal, eg. `[音楽|おん|がく]`.
+    Detailed,
+
+    /// All kanji blocks have a single reading for all literals, eg. `[音楽|おんがく]`.
+    Merged,
+
+    /// Both detailed and merged kanji blocks are used.
+    Mixed,
+}
+
 impl<T> Furigana<T>
 where
     T: AsRef<str>,
@@ -49,6 +78,12 @@ where
         self.0.as_ref()
     }
 
+    /// Returns a new furigana wrapper borrowing the furigana data as `&str`.
+    #[inline]
+    pub fn to_borrowed(&self) -> Furigana<&str> {
+        Furigana(self.raw())
+    }
+
     /// Returns a generalized furigana parser over the furigana data.
     #[inline]
     pub fn gen_parser(&self) -> FuriParserGen {
@@ -79,6 +114,29 @@ where
         self.kanji().to_string()
     }
 
+    /// Returns the kanji reading of the furigana. Unlike [`Furigana::kanji_str`] this only
+    /// allocates if the furigana has kanji blocks that need to be stripped.
+    #[inline]
+    pub fn as_plain_kanji(&self) -> Cow<'_, str> {
+        if self.has_kanji() {
+            Cow::Owned(self.kanji_str())
+        } else {
+            Cow::Borrowed(self.raw())
+        }
+    }
+
+    /// Returns `true` if `s` is the kana reading of the furigana.
+    #[inline]
+    pub fn is_kana_of(&self, s: &str) -> bool {
+        self.kana().eq_str(s)
+    }
+
+    /// Returns `true` if `s` is the kanji reading of the furigana.
+    #[inline]
+    pub fn is_kanji_of(&self, s: &str) -> bool {
+        self.kanji().eq_str(s)
+    }
+
     /// Returns `true` if the Furigana has at least one kana segment.
     #[inline]
     pub fn has_kana(&self) -> bool {
@@ -91,12 +149,40 @@ where
         self.gen_parser().any(|i| i.1)
     }
 
+    /// Returns `true` if the furigana has no kanji blocks.
+    #[inline]
+    pub fn is_kana_only(&self) -> bool {
+        self.gen_parser().all(|i| !i.1)
+    }
+
     /// Returns `true` if the furgiana has a given kanji literal.
     #[inline]
     pub fn contains_kanji(&self, kanji: char) -> bool {
         self.raw().contains(kanji)
     }
 
+    /// Returns the `n`th literal of all kanji blocks, skipping kana segments.
+    pub fn kanji_char_at(&self, n: usize) -> Option<char> {
+        self.segments()
+            .filter_map(|seg| seg.as_kanji().map(|k| *k.literals()))
+            .flat_map(|lits| lits.chars())
+            .nth(n)
+    }
+
+    /// Returns the reading assigned to the first occurrence of `kanji`. If the block containing
+    /// `kanji` isn't detailed, the whole reading of the block is returned instead.
+    pub fn reading_of_kanji(&self, kanji: char) -> Option<String> {
+        self.segments().find_map(|seg| {
+            let k = seg.as_kanji()?;
+            let pos = k.literals().chars().position(|c| c == kanji)?;
+            if k.is_detailed() {
+                k.reading_at(pos).map(|r| r.to_string())
+            } else {
+                Some(k.full_reading())
+            }
+        })
+    }
+
     /// Returns a `Reading` of the furigana.
     #[inline]
     pub fn to_reading(&self) -> Reading {
@@ -126,18 +212,228 @@ where
             .map(|i| UncheckedFuriParser::from_seg_str(i.0, i.1))
     }
 
+    /// Returns an iterator over all kanji blocks with their literals and full kana reading.
+    #[inline]
+    pub fn kanji_readings(&self) -> impl Iterator<Item = (&str, String)> {
+        self.kanji_segments().filter_map(|seg| match seg {
+            SegmentRef::Kanji(kanji) => Some((*kanji.literals(), kanji.full_reading())),
+            SegmentRef::Kana(_) => None,
+        })
+    }
+
     /// Returns an iterator over all segments of the furigana.
     #[inline]
     pub fn segments(&self) -> UncheckedFuriParser {
         FuriParser::new(self.raw()).unchecked()
     }
 
+    /// Parses all segments of the furigana and returns the error of the first malformed block
+    /// instead of silently falling back like [`Furigana::segments`] does.
+    #[inline]
+    pub fn try_segments(&self) -> Result<Vec<SegmentRef<'_>>, FuriParseError> {
+        FuriParser::new(self.raw()).to_vec()
+    }
+
+    /// Returns an iterator over all segments along with the byte range each segment occupies in
+    /// the kana reading returned by [`Furigana::kana_str`].
+    pub fn segments_with_kana_range(&self) -> impl Iterator<Item = (SegmentRef<'_>, Range<usize>)> {
+        let mut pos = 0;
+        self.gen_parser().map(move |(sub, is_kanji)| {
+            let seg = UncheckedFuriParser::from_seg_str(sub, is_kanji);
+            // Use the same parser as `kana_str` so blocks without a reading are counted the same.
+            let len = FuriToReadingParser::new(sub, true).len();
+            let range = pos..pos + len;
+            pos += len;
+            (seg, range)
+        })
+    }
+
+    /// Returns a map of each kanji literal to all readings it has within the furigana. Kanji
+    /// blocks that don't have a reading per literal (eg. `[音楽|おんがく]`) are ignored as the
+    /// reading of a single literal can't be known.
+    pub fn reading_map(&self) -> HashMap<char, Vec<String>> {
+        let mut map: HashMap<char, Vec<String>> = HashMap::new();
+
+        for seg in self.kanji_segments() {
+            let Some(kanji) = seg.as_kanji() else {
+                continue;
+            };
+            for (lit, reading) in kanji.literal_readings() {
+                let mut lit_chars = lit.chars();
+                let (Some(lit), None) = (lit_chars.next(), lit_chars.next()) else {
+                    continue;
+                };
+
+                let readings = map.entry(lit).or_default();
+                if !readings.contains(&reading) {
+                    readings.push(reading);
+                }
+            }
+        }
+
+        map
+    }
+
+    /// Splits the furigana around the first kanji segment containing `kanji` and returns the parts
+    /// before and after that segment. Returns `None` if there is no such segment.
+    pub fn split_at_kanji(&self, kanji: char) -> Option<(Furigana<&str>, Furigana<&str>)> {
+        let raw = self.raw();
+        let (range, _, _) = self
+            .gen_parser()
+            .with_offsets()
+            .find(|(_, txt, is_kanji)| {
+                *is_kanji
+                    && UncheckedFuriParser::from_seg_str(txt, true)
+                        .as_kanji()
+                        .is_some_and(|k| k.literals().contains(kanji))
+            })?;
+        Some((Furigana(&raw[..range.start]), Furigana(&raw[range.end..])))
+    }
+
+    /// Splits trailing sentence punctuation (`。`, `！` and `？`) off the furigana and returns the
+    /// furigana without the punctuation and the punctuation itself.
+    pub fn split_trailing_punct(&self) -> (Furigana<&str>, &str) {
+        let raw = self.raw();
+        let Some((last, false)) = self.gen_parser().next_back() else {
+            return (Furigana(raw), "");
+        };
+
+        let trimmed = last.trim_end_matches(SENTENCE_ENDS);
+        let split = raw.len() - (last.len() - trimmed.len());
+        (Furigana(&raw[..split]), &raw[split..])
+    }
+
+    /// Returns an iterator over all sentences of the furigana. Sentences end after a run of `。`,
+    /// `！` or `？` which is kept at the end of the sentence.
+    pub fn sentences(&self) -> impl Iterator<Item = Furigana<&str>> {
+        let raw = self.raw();
+
+        let ends = self
+            .gen_parser()
+            .with_offsets()
+            .filter(|(_, _, is_kanji)| !is_kanji)
+            .flat_map(|(range, txt, _)| {
+                let mut chars = txt.char_indices().peekable();
+                iter::from_fn(move || loop {
+                    let (pos, c) = chars.next()?;
+                    let run_end = match chars.peek() {
+                        Some((_, next)) => !SENTENCE_ENDS.contains(next),
+                        None => true,
+                    };
+                    if SENTENCE_ENDS.contains(&c) && run_end {
+                        return Some(range.start + pos + c.len_utf8());
+                    }
+                })
+            })
+            .chain(iter::once(raw.len()));
+
+        let mut start = 0;
+        ends.filter_map(move |end| {
+            if end <= start {
+                return None;
+            }
+            let sentence = &raw[start..end];
+            start = end;
+            Some(Furigana(sentence))
+        })
+    }
+
+    /// Returns `true` if the furigana has kanji blocks with readings that look corrupt. These are
+    /// readings containing kanji and blank readings in blocks with multiple readings. Single
+    /// empty readings like `[6|]` are valid and not reported.
+    pub fn has_suspicious_readings(&self) -> bool {
+        self.kanji_segments().any(|seg| {
+            let Some(kanji) = seg.as_kanji() else {
+                return false;
+            };
+            let readings = kanji.readings();
+            readings
+                .iter()
+                .any(|r| r.has_kanji() || (readings.len() > 1 && r.trim().is_empty()))
+        })
+    }
+
+    /// Returns the segment that renders the character at `char_idx` of the kana reading returned
+    /// by [`Furigana::kana_str`]. Returns `None` if `char_idx` is out of bounds.
+    pub fn segment_at_kana_char(&self, char_idx: usize) -> Option<SegmentRef<'_>> {
+        let mut end = 0;
+        self.gen_parser()
+            .find(|(sub, _)| {
+                end += FuriToReadingParser::new(sub, true).char_count();
+                char_idx < end
+            })
+            .map(|(sub, is_kanji)| UncheckedFuriParser::from_seg_str(sub, is_kanji))
+    }
+
     /// Returns the amount of reading segments.
     #[inline]
     pub fn segment_count(&self) -> usize {
         self.gen_parser().count()
     }
 
+    /// Returns the amount of detailed kanji blocks, merged kanji blocks and kana segments. Kanji
+    /// blocks with a single literal and reading are counted as detailed.
+    pub fn style_histogram(&self) -> (usize, usize, usize) {
+        let mut histogram = (0, 0, 0);
+
+        for (sub, is_kanji) in self.gen_parser() {
+            if !is_kanji {
+                histogram.2 += 1;
+                continue;
+            }
+
+            match UncheckedFuriParser::from_seg_str(sub, true).as_kanji() {
+                Some(kanji) if kanji.is_detailed() => histogram.0 += 1,
+                Some(_) => histogram.1 += 1,
+                // Reading-only blocks are parsed as kana.
+                None => histogram.2 += 1,
+            }
+        }
+
+        histogram
+    }
+
+    /// Returns whether the kanji blocks use detailed or merged readings. Blocks with a single
+    /// literal are both and don't influence the result. Furigana without such blocks is
+    /// considered [`ReadingStyle::Detailed`].
+    pub fn reading_style(&self) -> ReadingStyle {
+        let mut detailed = false;
+        let mut merged = false;
+
+        for seg in self.segments() {
+            let Some(kanji) = seg.as_kanji() else {
+                continue;
+            };
+            if kanji.lit_count() < 2 {
+                continue;
+            }
+
+            if kanji.is_detailed() {
+                detailed = true;
+            } else {
+                merged = true;
+            }
+        }
+
+        match (detailed, merged) {
+            (true, true) => ReadingStyle::Mixed,
+            (false, true) => ReadingStyle::Merged,
+            _ => ReadingStyle::Detailed,
+        }
+    }
+
+    /// Returns the amount of kanji segments.
+    #[inline]
+    pub fn kanji_segment_count(&self) -> usize {
+        self.gen_parser().filter(|(_, kanji)| *kanji).count()
+    }
+
+    /// Returns the amount of kana segments.
+    #[inline]
+    pub fn kana_segment_count(&self) -> usize {
+        self.gen_parser().filter(|(_, kanji)| !*kanji).count()
+    }
+
     /// Converts the sequence into a Vec of its segments.
     #[inline]
     pub fn as_segments(&self) -> Vec<Segment> {
@@ -196,11 +492,195 @@ where
         Furigana(out_buf)
     }
 
+    /// Returns a new furigana value only containing the kanji blocks of the furigana. Kanji blocks
+    /// without a reading are kept as well.
+    #[inline]
+    pub fn kanji_only(&self) -> Furigana<String> {
+        self.filter_parts(true)
+    }
+
+    /// Returns a new furigana value only containing the kana parts of the furigana.
+    #[inline]
+    pub fn kana_only(&self) -> Furigana<String> {
+        self.filter_parts(false)
+    }
+
+    fn filter_parts(&self, kanji: bool) -> Furigana<String> {
+        let out: String = self
+            .gen_parser()
+            .filter(|i| i.1 == kanji)
+            .map(|i| i.0)
+            .collect();
+        Furigana(out)
+    }
+
     /// Converts the furigana to a Furigana<String>
     #[inline]
     pub fn as_owned(&self) -> Furigana<String> {
         Furigana(self.raw().to_string())
     }
+
+    /// Converts the kana reading of the furigana to romaji. Returns `None` if the reading contains
+    /// characters that can't be converted.
+    #[cfg(feature = "hiragana")]
+    #[inline]
+    pub fn romaji(&self) -> Option<String> {
+        crate::hiragana::to_romaji(&self.kana_str())
+    }
+
+    /// Returns an iterator over the romaji of the kana reading of each segment. Yields `None` for
+    /// segments whose reading can't be converted to romaji.
+    #[cfg(feature = "hiragana")]
+    pub fn romaji_segments(&self) -> impl Iterator<Item = Option<String>> + '_ {
+        use crate::hiragana::to_romaji;
+        self.segments()
+            .map(|seg| to_romaji(&seg.get_kana_reading()))
+    }
+
+    /// Renders the furigana as HTML ruby markup, eg. `<ruby>音楽<rt>おんがく</rt></ruby>が`. Kanji
+    /// blocks without a reading are rendered as plain text.
+    #[inline]
+    pub fn to_ruby_html(&self) -> String {
+        self.to_ruby_html_with(false)
+    }
+
+    /// Like [`Furigana::to_ruby_html`] but renders kanji blocks without a reading as ruby with an
+    /// empty `<rt>`, eg. `<ruby>A<rt></rt></ruby>`, if `empty_rt` is `true`.
+    pub fn to_ruby_html_with(&self, empty_rt: bool) -> String {
+        // Readings are passed through unchanged, so rendering never fails.
+        self.render_ruby_html(empty_rt, Some).unwrap()
+    }
+
+    /// Renders the furigana as HTML ruby markup with the reading of each kanji block converted
+    /// using `rt`. All text is HTML escaped. Returns `None` if `rt` fails for a reading.
+    fn render_ruby_html<F>(&self, empty_rt: bool, rt: F) -> Option<String>
+    where
+        F: Fn(String) -> Option<String>,
+    {
+        let mut out = String::with_capacity(self.raw().len() * 2);
+        for seg in self.segments() {
+            let Some(kanji) = seg.as_kanji() else {
+                push_html_escaped(&mut out, seg.main_reading());
+                continue;
+            };
+
+            let reading = kanji.full_reading();
+            if reading.is_empty() && !empty_rt {
+                push_html_escaped(&mut out, kanji.literals());
+                continue;
+            }
+
+            let reading = if reading.is_empty() {
+                reading
+            } else {
+                rt(reading)?
+            };
+            out.push_str("<ruby>");
+            push_html_escaped(&mut out, kanji.literals());
+            out.push_str("<rt>");
+            push_html_escaped(&mut out, &reading);
+            out.push_str("</rt></ruby>");
+        }
+        Some(out)
+    }
+
+    /// Renders the furigana as HTML ruby markup with the romaji of each kanji block in its `<rt>`,
+    /// eg. `<ruby>音楽<rt>ongaku</rt></ruby>が`. Kanji blocks without a reading are rendered as
+    /// plain text. Returns `None` if a reading can't be converted to romaji.
+    #[cfg(feature = "hiragana")]
+    pub fn to_ruby_romaji_html(&self) -> Option<String> {
+        self.render_ruby_html(false, |reading| crate::hiragana::to_romaji(&reading))
+    }
+
+    /// Merges two annotations of the same text by taking the more detailed reading for each
+    /// segment. Returns an error if the segments of both furigana don't have the same text or
+    /// their non empty readings don't match.
+    pub fn merge_with<U: AsRef<str>>(&self, other: &Furigana<U>) -> Result<Furigana<String>, ()> {
+        let mut out = String::with_capacity(self.raw().len().max(other.raw().len()));
+        let mut enc = FuriEncoder::new(&mut out);
+
+        let mut other_segs = other.segments();
+        for seg in self.segments() {
+            let other_seg = other_segs.next().ok_or(())?;
+            if seg.main_reading() != other_seg.main_reading() {
+                return Err(());
+            }
+
+            let (Some(kanji), Some(other_kanji)) = (seg.as_kanji(), other_seg.as_kanji()) else {
+                // Prefer the segment with a reading.
+                enc.write_seg(if seg.is_kanji() { seg } else { other_seg });
+                continue;
+            };
+
+            let (reading, other_reading) = (kanji.full_reading(), other_kanji.full_reading());
+            if !reading.is_empty() && !other_reading.is_empty() && reading != other_reading {
+                return Err(());
+            }
+
+            let detail = (kanji.reading_count(), !reading.is_empty());
+            let other_detail = (other_kanji.reading_count(), !other_reading.is_empty());
+            let more = if detail >= other_detail {
+                kanji
+            } else {
+                other_kanji
+            };
+
+            enc.write_kanji(more);
+        }
+
+        if other_segs.next().is_some() {
+            return Err(());
+        }
+
+        Ok(Furigana(out))
+    }
+
+    /// Returns the furigana in a canonical encoding which is suitable to be used as key in maps.
+    /// The canonical form is built with a fixed rule:
+    /// - kana segments and kanji blocks without a reading are merged into a single kana segment.
+    /// - adjacent detailed kanji blocks are grouped into a single detailed block.
+    /// - all other kanji blocks are written as a block with a single merged reading.
+    ///
+    /// For detailed input, two canonicalized values are equal if and only if both have the same
+    /// text and the same reading for each kanji literal.
+    pub fn canonicalize(&self) -> Furigana<String> {
+        let mut out = String::with_capacity(self.raw().len());
+        let mut enc = FuriEncoder::new(&mut out);
+        let mut kana = String::new();
+        let mut lits = String::new();
+        let mut readings = vec![];
+
+        for seg in self.segments() {
+            let Some(kanji) = seg.as_kanji() else {
+                flush_detailed(&mut enc, &mut lits, &mut readings);
+                kana.push_str(seg.main_reading());
+                continue;
+            };
+
+            if kanji.readings().iter().all(|r| r.is_empty()) {
+                flush_detailed(&mut enc, &mut lits, &mut readings);
+                kana.push_str(kanji.literals());
+                continue;
+            }
+
+            if !kana.is_empty() {
+                enc.write_kana(&kana);
+                kana.clear();
+            }
+
+            if kanji.is_detailed() {
+                lits.push_str(kanji.literals());
+                readings.extend_from_slice(kanji.readings());
+            } else {
+                flush_detailed(&mut enc, &mut lits, &mut readings);
+                enc.write_block(kanji.literals(), &kanji.full_reading());
+            }
+        }
+
+        flush_detailed(&mut enc, &mut lits, &mut readings);
+        enc.write_kana(&kana);
+        Furigana(out)
+    }
 }
 
 impl<T> Furigana<T> {
@@ -226,6 +706,15 @@ impl<T> Furigana<T> {
         self.0
     }
 
+    /// Converts the furigana into a furigana value holding a [`Cow`].
+    #[inline]
+    pub fn into_cow<'a>(self) -> Furigana<Cow<'a, str>>
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        Furigana(self.0.into())
+    }
+
     /// Create a new Furigana value with a given encoded furi string as value which doesn't get checked.
     #[inline]
     pub fn new_unchecked(furi: T) -> Self {
@@ -240,6 +729,66 @@ impl<T> Furigana<T> {
 }
 
 impl Furigana<String> {
+    /// Encodes the given readings into a new furigana value.
+    pub fn from_readings(readings: &[ReadingRef<'_>]) -> Self {
+        let cap = readings
+            .iter()
+            .map(|r| r.kana().len() + r.kanji().map(|k| k.len() + 3).unwrap_or(0))
+            .sum();
+
+        let mut buf = String::with_capacity(cap);
+        let mut encoder = FuriEncoder::new(&mut buf);
+        for reading in readings {
+            encoder.write_reading(reading);
+        }
+        Furigana(buf)
+    }
+
+    /// Encodes a list of `(text, reading)` pairs into a new furigana value. Pairs with a reading
+    /// are encoded as kanji blocks and pairs without one as kana.
+    pub fn from_pairs(pairs: &[(String, Option<String>)]) -> Self {
+        let cap = pairs
+            .iter()
+            .map(|(text, reading)| text.len() + reading.as_ref().map(|r| r.len() + 3).unwrap_or(0))
+            .sum();
+
+        let mut buf = String::with_capacity(cap);
+        let mut encoder = FuriEncoder::new(&mut buf);
+        for (text, reading) in pairs {
+            match reading {
+                Some(reading) => encoder.write_block(text, reading),
+                None => encoder.write_kana(text),
+            }
+        }
+        Furigana(buf)
+    }
+
+    /// Builds furigana from parallel slices of text runs and their readings. Runs with readings are
+    /// encoded as kanji blocks and runs without readings as kana. Returns an error if both slices
+    /// have a different length or the readings of a run don't match up with its literals.
+    pub fn interleave(kanji_runs: &[&str], readings: &[&[&str]]) -> Result<Self, ()> {
+        if kanji_runs.len() != readings.len() {
+            return Err(());
+        }
+
+        let mut buf = String::new();
+        let mut encoder = FuriEncoder::new(&mut buf);
+        for (run, run_readings) in kanji_runs.iter().zip(readings) {
+            if run_readings.is_empty() {
+                encoder.write_kana(run);
+                continue;
+            }
+
+            let kanji = KanjiRef::new(run, run_readings);
+            if !kanji.is_valid() {
+                return Err(());
+            }
+            encoder.write_kanji(kanji);
+        }
+
+        Ok(Furigana(buf))
+    }
+
     /// Pushes a segment to the end of the furigana sequence.
     #[inline]
     pub fn push_segment<S>(&mut self, seg: S)
@@ -271,6 +820,93 @@ impl Furigana<String> {
     {
         self.0.push_str(seg.as_ref());
     }
+
+    /// Encodes the given reading and pushes it to the end of the furigana.
+    #[inline]
+    pub fn push_reading<R>(&mut self, reading: R)
+    where
+        R: AsReadingRef,
+    {
+        FuriEncoder::new(&mut self.0).write_reading(reading);
+    }
+
+    /// Like [`Furigana::push_reading`] but returns an error and doesn't push anything if the kana
+    /// of `reading` isn't a valid kana reading or its kanji is empty or contains furigana syntax.
+    pub fn try_push_reading<R>(&mut self, reading: R) -> Result<(), ()>
+    where
+        R: AsReadingRef,
+    {
+        let r = reading.as_reading_ref();
+        if !r.kana().is_valid_reading() {
+            return Err(());
+        }
+        if let Some(kanji) = r.kanji() {
+            if kanji.is_empty() || kanji.contains(['[', ']', '|']) {
+                return Err(());
+            }
+        }
+        self.push_reading(r);
+        Ok(())
+    }
+
+    /// Consumes the furigana and returns an iterator over its owned segments. Segments are
+    /// parsed lazily, so unlike `as_segments` there is no Vec of all segments held next to the
+    /// furigana string.
+    pub fn into_segments(self) -> impl Iterator<Item = Segment> {
+        let raw = self.0;
+        let mut pos = 0;
+        iter::from_fn(move || {
+            // Each segment ends at a position the parser can be restarted at.
+            let (range, txt, kanji) = FuriParserGen::new(&raw[pos..]).with_offsets().next()?;
+            let seg = UncheckedFuriParser::from_seg_str(txt, kanji).to_owned();
+            pos += range.end;
+            Some(seg)
+        })
+    }
+
+    /// Replaces the segment at `pos` with the segment returned by `f` without re-encoding the
+    /// other segments. Returns `false` if `pos` is out of bounds.
+    pub fn edit_segment<F>(&mut self, pos: usize, f: F) -> bool
+    where
+        F: FnOnce(Segment) -> Segment,
+    {
+        let Some((range, txt, kanji)) = self.gen_parser().with_offsets().nth(pos) else {
+            return false;
+        };
+
+        let seg = UncheckedFuriParser::from_seg_str(txt, kanji).to_owned();
+        let new_seg = f(seg).encode();
+        self.0.replace_range(range, &new_seg);
+        true
+    }
+}
+
+impl<'a> Furigana<Cow<'a, str>> {
+    /// Create a new Furigana value from a [`Cow`] which doesn't get checked.
+    #[inline]
+    pub fn from_cow(furi: Cow<'a, str>) -> Self {
+        Self(furi)
+    }
+
+    /// Converts the furigana into a Furigana<String>, only cloning the data if it is borrowed.
+    #[inline]
+    pub fn into_owned(self) -> Furigana<String> {
+        Furigana(self.0.into_owned())
+    }
+}
+
+impl<'a> From<Furigana<&'a str>> for Furigana<Cow<'a, str>> {
+    #[inline]
+    fn from(value: Furigana<&'a str>) -> Self {
+        value.into_cow()
+    }
+}
+
+impl From<Furigana<String>> for Furigana<Cow<'_, str>> {
+    #[inline]
+    fn from(value: Furigana<String>) -> Self {
+        value.into_cow()
+    }
 }
 
 impl<T: AsSegment> From<FuriSequence<T>> for Furigana<String> {
@@ -287,10 +923,27 @@ impl<'a, T: AsRef<str>> Into<FuriSequence<SegmentRef<'a>>> for &'a Furigana<T> {
     }
 }
 
-impl<T: AsRef<str>> From<T> for Furigana<T> {
+/// Validates the furigana before wrapping it. Use [`Furigana::new_unchecked`] to create a
+/// Furigana value without validation.
+impl<'a> TryFrom<&'a str> for Furigana<&'a str> {
+    type Error = FuriParseError;
+
+    #[inline]
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        FuriParser::validate(value)?;
+        Ok(Self(value))
+    }
+}
+
+/// Validates the furigana before wrapping it. Use [`Furigana::new_unchecked`] to create a
+/// Furigana value without validation.
+impl TryFrom<String> for Furigana<String> {
+    type Error = FuriParseError;
+
     #[inline]
-    fn from(value: T) -> Self {
-        Self(value)
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        FuriParser::validate(&value)?;
+        Ok(Self(value))
     }
 }
 
@@ -313,11 +966,7 @@ impl<S: AsSegment> FromIterator<S> for Furigana<String> {
 impl<S: AsSegment> Extend<S> for Furigana<String> {
     #[inline]
     fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
-        let iter = iter.into_iter();
-        self.0.reserve(iter.size_hint().0 * 6);
-        for s in iter {
-            self.push_segment(s);
-        }
+        FuriEncoder::new(&mut self.0).extend(iter);
     }
 }
 
@@ -403,11 +1052,36 @@ where
     }
 }
 
+/// Pushes `s` to `out` with the HTML special characters `<`, `>`, `&` and `"` escaped.
+fn push_html_escaped(out: &mut String, s: &str) {
+    for c in s.chars() {
+        match c {
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '&' => out.push_str("&amp;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+}
+
+/// Writes the grouped detailed kanji literals and their readings as a single kanji block.
+fn flush_detailed(enc: &mut FuriEncoder, lits: &mut String, readings: &mut Vec<&str>) {
+    if lits.is_empty() {
+        return;
+    }
+    enc.write_kanji(KanjiRef::new(lits, readings));
+    lits.clear();
+    readings.clear();
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::reading::ReadingRef;
+    use crate::furi::parse::FuriParseErrorKind;
     use criterion::black_box;
+    use std::collections::HashSet;
+    use test_case::test_case;
 
     #[test]
     fn test_furigana() {
@@ -426,6 +1100,8 @@ mod test {
         assert!(furi.contains_kanji('音'));
         assert!(!furi.contains_kanji('弱'));
         assert_eq!(furi.segment_count(), 4);
+        assert_eq!(furi.kanji_segment_count(), 2);
+        assert_eq!(furi.kana_segment_count(), 2);
         assert_eq!(furi.segment_at(black_box(2)), furi.segments().nth(2));
         assert_eq!(furi.segment_at(1), Some(SegmentRef::new_kana("が")));
         assert_eq!(
@@ -506,6 +1182,565 @@ mod test {
         assert_eq!(new, Furigana("セックスが[大好|だい|す]きです"))
     }
 
+    #[test_case("[音|おん][楽|がく]が[好|す]き", "[音楽|おん|がく]が[好|す]き"; "split kanji")]
+    #[test_case("[毎朝|まい|あさ][6|][時|じ]に", "[毎|まい][朝|あさ]6[時|じ]に"; "empty kanji")]
+    #[test_case("[大|だい][丈|じょう][夫|ぶ]", "[大丈|だい|じょう][夫|ぶ]"; "three kanji")]
+    fn test_canonicalize(a: &str, b: &str) {
+        let a = Furigana(a).canonicalize();
+        let b = Furigana(b).canonicalize();
+        assert_eq!(a, b);
+        assert_eq!(a.canonicalize(), a);
+
+        let mut set = HashSet::new();
+        set.insert(a);
+        assert!(set.contains(&b));
+    }
+
+    #[test_case("[音楽|おん|がく]", "[音楽|おんがく]"; "detailed and merged")]
+    #[test_case("[音|おん][楽|がく]", "[音|おん][楽|らく]"; "different reading")]
+    #[test_case("[音|おん][楽|がく]が", "[音|おん][楽|がく]を"; "different kana")]
+    #[test_case("[音楽|おん|がく]", "[音|おと][楽|がく]"; "different literal reading")]
+    #[test_case("[毎朝|まい|あさ]", "[毎朝|まい|あさ]6"; "different text")]
+    fn test_canonicalize_different(a: &str, b: &str) {
+        let a = Furigana(a).canonicalize();
+        let b = Furigana(b).canonicalize();
+        assert_ne!(a, b);
+    }
+
+    #[test_case("[音|おん][楽|がく]が[好|す]き", "[音楽|おん|がく]が[好|す]き"; "split detailed")]
+    #[test_case("[毎|まい][朝|あさ][6|][時|じ]に", "[毎朝|まい|あさ]6[時|じ]に"; "empty reading")]
+    #[test_case("[音|おん][楽|がく][大学|だいがく]", "[音楽|おん|がく][大学|だいがく]"; "merged block")]
+    #[test_case("[音楽大|おんがく|だい]", "[音楽大|おんがくだい]"; "malformed block")]
+    #[test_case("かな", "かな"; "kana only")]
+    fn test_canonicalize_form(src: &str, exp: &str) {
+        assert_eq!(Furigana(src).canonicalize(), Furigana(exp));
+    }
+
+    #[test]
+    fn test_canonicalize_reading_eq() {
+        // Equal canonical forms have equal text and literal readings and vice versa.
+        let inputs = [
+            "[音|おん][楽|がく]が[好|す]き",
+            "[音楽|おん|がく]が[好|す]き",
+            "[音楽|おん|がく]が[好|す][き|]",
+            "[音楽|おん|がく][が|][好|す]き",
+            "[音|おと][楽|がく]が[好|す]き",
+            "[音楽|おん|がく]を[好|す]き",
+            "[音楽|おん|がく]が[好|この]き",
+        ];
+        // Text, kana and the reading of each kanji literal with a reading.
+        let readings = |furi: &str| {
+            let furi = Furigana(furi);
+            let lits: Vec<_> = furi
+                .segments()
+                .filter_map(|seg| {
+                    seg.as_kanji()
+                        .map(|k| k.literal_readings().collect::<Vec<_>>())
+                })
+                .flatten()
+                .filter(|(_, r)| !r.is_empty())
+                .collect();
+            (furi.kanji_str(), furi.kana_str(), lits)
+        };
+
+        for a in inputs {
+            for b in inputs {
+                let canonical_eq = Furigana(a).canonicalize() == Furigana(b).canonicalize();
+                let reading_eq = readings(a) == readings(b);
+                assert_eq!(canonical_eq, reading_eq, "{a} {b}");
+            }
+        }
+    }
+
+    #[test_case("[音楽|おん|がく]が[好|す]き", "[音楽|おん|がく][好|す]", "がき")]
+    #[test_case("[音楽|おん|がく]", "[音楽|おん|がく]", "")]
+    #[test_case("かな", "", "かな")]
+    #[test_case("[A|]と[B|び]", "[A|][B|び]", "と"; "empty reading")]
+    fn test_kanji_kana_only(src: &str, kanji: &str, kana: &str) {
+        let furi = Furigana(src);
+        assert_eq!(furi.kanji_only(), Furigana(kanji));
+        assert_eq!(furi.kana_only(), Furigana(kana));
+    }
+
+    #[test_case("[音楽|おん|がく]が[好|す]き")]
+    #[test_case("この[人|ひと]が[嫌|きら]いです。")]
+    #[test_case("[拝金主義|はい|きん|しゅ|ぎ]は[問題|もん|だい]")]
+    #[test_case(""; "empty")]
+    #[test_case("[6|][時|じ]"; "empty reading")]
+    #[test_case("[ 良|]かな"; "empty reading with space")]
+    fn test_segments_with_kana_range(furi: &str) {
+        let furi = Furigana(furi);
+        let kana = furi.kana_str();
+
+        let mut last_end = 0;
+        for (seg, range) in furi.segments_with_kana_range() {
+            assert_eq!(range.start, last_end);
+            assert_eq!(&kana[range.clone()], Furigana(seg.encode()).kana_str());
+            last_end = range.end;
+        }
+        assert_eq!(last_end, kana.len());
+    }
+
+    #[cfg(feature = "hiragana")]
+    #[test]
+    fn test_romaji_segments() {
+        let furi = Furigana("[音楽|おん|がく]が[大好|だい|す]きです。");
+        let romaji: Vec<_> = furi.romaji_segments().collect();
+        assert_eq!(
+            romaji,
+            vec![
+                Some("ongaku".to_string()),
+                Some("ga".to_string()),
+                Some("daisu".to_string()),
+                None,
+            ]
+        );
+
+        let furi = Furigana("[東京|とう|きょう]に[行|い]く");
+        let romaji: Vec<_> = furi.romaji_segments().map(Option::unwrap).collect();
+        assert_eq!(romaji, vec!["toukyou", "ni", "i", "ku"]);
+    }
+
+    #[cfg(feature = "hiragana")]
+    #[test]
+    fn test_to_ruby_romaji_html() {
+        let furi = Furigana("[音楽|おん|がく]が[好|す]きです");
+        assert_eq!(
+            furi.to_ruby_romaji_html().as_deref(),
+            Some("<ruby>音楽<rt>ongaku</rt></ruby>が<ruby>好<rt>su</rt></ruby>きです")
+        );
+        assert_eq!(
+            Furigana("[2|]つの[東京|とうきょう]")
+                .to_ruby_romaji_html()
+                .as_deref(),
+            Some("2つの<ruby>東京<rt>toukyou</rt></ruby>")
+        );
+        assert_eq!(Furigana("[音楽|ONGAKU]").to_ruby_romaji_html(), None);
+        assert_eq!(
+            Furigana("a<b&[音|おと]").to_ruby_romaji_html().as_deref(),
+            Some("a&lt;b&amp;<ruby>音<rt>oto</rt></ruby>")
+        );
+    }
+
+    #[cfg(feature = "hiragana")]
+    #[test]
+    fn test_romaji() {
+        let furi = Furigana("[音楽|おん|がく]が[大好|だい|す]きです");
+        assert_eq!(furi.romaji().as_deref(), Some("ongakugadaisukidesu"));
+        assert_eq!(
+            Furigana("[東京|とう|きょう]").romaji().as_deref(),
+            Some("toukyou")
+        );
+        assert_eq!(Furigana("[音楽|おんがく]。").romaji(), None);
+        assert_eq!(furi.romaji(), furi.to_reading().romaji());
+    }
+
+    #[test]
+    fn test_is_kana_kanji_of() {
+        let furi = Furigana("[音楽|おん|がく]が[大好|だい|す]きです");
+        assert!(furi.is_kana_of("おんがくがだいすきです"));
+        assert!(!furi.is_kana_of("音楽が大好きです"));
+        assert!(furi.is_kanji_of("音楽が大好きです"));
+        assert!(!furi.is_kanji_of("おんがくがだいすきです"));
+        assert!(!furi.is_kanji_of("音楽が大好き"));
+    }
+
+    #[test_case("[音楽|おん|がく]が[好|す]き", false; "clean")]
+    #[test_case("[6|][時|じ]に[起|お]きていた", false; "empty reading")]
+    #[test_case("[音楽|おん|楽]が[好|す]き", true; "kanji in reading")]
+    #[test_case("[音楽|おん楽]", true; "kanji in single reading")]
+    #[test_case("[音楽|おん|]が[好|す]き", true; "blank detailed reading")]
+    fn test_has_suspicious_readings(furi: &str, exp: bool) {
+        assert_eq!(Furigana(furi).has_suspicious_readings(), exp);
+    }
+
+    #[test]
+    fn test_segment_at_kana_char_empty_reading() {
+        let furi = Furigana("[6|][時|じ]");
+        assert_eq!(furi.kana_str(), "6じ");
+        assert_eq!(
+            furi.segment_at_kana_char(0),
+            Some(SegmentRef::new_kanji("6", &[""]))
+        );
+        assert_eq!(
+            furi.segment_at_kana_char(1),
+            Some(SegmentRef::new_kanji("時", &["じ"]))
+        );
+        assert_eq!(furi.segment_at_kana_char(2), None);
+    }
+
+    #[test]
+    fn test_segment_at_kana_char() {
+        let furi = Furigana("[音楽|おん|がく]が[大好|だい|す]きです");
+        let ongaku = SegmentRef::new_kanji("音楽", &["おん", "がく"]);
+        let daisu = SegmentRef::new_kanji("大好", &["だい", "す"]);
+
+        assert_eq!(furi.segment_at_kana_char(0), Some(ongaku.clone()));
+        assert_eq!(furi.segment_at_kana_char(3), Some(ongaku));
+        assert_eq!(furi.segment_at_kana_char(4), Some(SegmentRef::Kana("が")));
+        assert_eq!(furi.segment_at_kana_char(5), Some(daisu.clone()));
+        assert_eq!(furi.segment_at_kana_char(7), Some(daisu));
+        assert_eq!(
+            furi.segment_at_kana_char(10),
+            Some(SegmentRef::Kana("きです"))
+        );
+        assert_eq!(furi.segment_at_kana_char(11), None);
+    }
+
+    #[test]
+    fn test_from_readings() {
+        let readings = [
+            ReadingRef::new_with_kanji("おんがく", "音楽"),
+            ReadingRef::new("が"),
+            ReadingRef::new_with_kanji("だいす", "大好"),
+            ReadingRef::new("きです"),
+        ];
+        let furi = Furigana::from_readings(&readings);
+        assert_eq!(furi, Furigana("[音楽|おんがく]が[大好|だいす]きです"));
+        assert!(furi.0.capacity() >= furi.0.len());
+
+        assert_eq!(Furigana::from_readings(&[]), Furigana(""));
+    }
+
+    #[test]
+    fn test_from_pairs() {
+        let pairs = [
+            ("音楽".to_string(), Some("おんがく".to_string())),
+            ("が".to_string(), None),
+            ("大好".to_string(), Some("だいす".to_string())),
+            ("きです".to_string(), None),
+        ];
+        let furi = Furigana::from_pairs(&pairs);
+        assert_eq!(furi, Furigana("[音楽|おんがく]が[大好|だいす]きです"));
+        assert!(furi.0.capacity() >= furi.0.len());
+
+        assert_eq!(Furigana::from_pairs(&[]), Furigana(""));
+    }
+
+    #[test]
+    fn test_interleave() {
+        let furi = Furigana::interleave(
+            &["音楽", "が", "好", "き"],
+            &[&["おん", "がく"], &[], &["す"], &[]],
+        );
+        assert_eq!(
+            furi,
+            Ok(Furigana("[音楽|おん|がく]が[好|す]き".to_string()))
+        );
+
+        let furi = Furigana::interleave(&["大好"], &[&["だいす"]]);
+        assert_eq!(furi, Ok(Furigana("[大好|だいす]".to_string())));
+
+        assert_eq!(Furigana::interleave(&["音楽"], &[]), Err(()));
+        assert_eq!(
+            Furigana::interleave(&["音楽"], &[&["お", "ん", "がく"]]),
+            Err(())
+        );
+        assert_eq!(Furigana::interleave(&[], &[]), Ok(Furigana(String::new())));
+    }
+
+    #[test]
+    fn test_kanji_readings() {
+        let furi = Furigana("[音楽|おん|がく]が[大好|だい|す]きです");
+        let readings: Vec<_> = furi.kanji_readings().collect();
+        assert_eq!(
+            readings,
+            vec![
+                ("音楽", "おんがく".to_string()),
+                ("大好", "だいす".to_string())
+            ]
+        );
+        assert_eq!(Furigana("かな").kanji_readings().count(), 0);
+    }
+
+    #[test]
+    fn test_split_at_kanji() {
+        let furi = Furigana("[音楽|おん|がく]が[好|す]きです");
+        let (before, after) = furi.split_at_kanji('好').unwrap();
+        assert_eq!(before, Furigana("[音楽|おん|がく]が"));
+        assert_eq!(after, Furigana("きです"));
+
+        let (before, after) = furi.split_at_kanji('楽').unwrap();
+        assert_eq!(before, Furigana(""));
+        assert_eq!(after, Furigana("が[好|す]きです"));
+
+        assert_eq!(furi.split_at_kanji('嫌'), None);
+        assert_eq!(furi.split_at_kanji('す'), None);
+    }
+
+    #[test]
+    fn test_reading_map() {
+        let furi = Furigana(
+            "[人|ひと]と[人間|にん|げん]と[日本人|に|ほん|じん]の[人|ひと][音楽|おんがく]",
+        );
+        let map = furi.reading_map();
+        assert_eq!(map.len(), 4);
+        assert_eq!(map[&'人'], vec!["ひと", "にん", "じん"]);
+        assert_eq!(map[&'間'], vec!["げん"]);
+        assert_eq!(map[&'日'], vec!["に"]);
+        assert_eq!(map[&'本'], vec!["ほん"]);
+        assert!(!map.contains_key(&'音'));
+    }
+
+    #[test_case("[音楽|おん|がく]が[好|す]き")]
+    #[test_case("[[1|],[2|]]のとき、[f|えふ]"; "with brackets")]
+    #[test_case("]が[好|す]][き"; "stray brackets")]
+    #[test_case(""; "empty")]
+    fn test_into_segments(furi: &str) {
+        let furi = Furigana(furi.to_string());
+        let exp = furi.as_segments();
+        let segments: Vec<Segment> = furi.into_segments().collect();
+        assert_eq!(segments, exp);
+    }
+
+    #[test]
+    fn test_edit_segment() {
+        let mut furi = Furigana("[音楽|おん|がく]が[好|す]き".to_string());
+        let edited = furi.edit_segment(2, |seg| {
+            let mut seg = seg;
+            if let Segment::Kanji(kanji) = &mut seg {
+                kanji.readings_mut()[0] = "この".to_string();
+            }
+            seg
+        });
+        assert!(edited);
+        assert_eq!(furi, Furigana("[音楽|おん|がく]が[好|この]き"));
+
+        assert!(furi.edit_segment(0, |_| Segment::new_kana("おんがく".to_string())));
+        assert_eq!(furi, Furigana("おんがくが[好|この]き"));
+
+        assert!(!furi.edit_segment(4, |seg| seg));
+        assert_eq!(furi, Furigana("おんがくが[好|この]き"));
+    }
+
+    #[test]
+    fn test_try_from() {
+        let src = "[音楽|おん|がく]が[好|す]き";
+        let furi: Furigana<&str> = src.try_into().unwrap();
+        assert_eq!(furi, Furigana(src));
+        let furi = Furigana::try_from(src.to_string()).unwrap();
+        assert_eq!(furi, Furigana(src));
+
+        let invalid = "が[音楽|お|ん|がく]";
+        let err = Furigana::try_from(invalid).unwrap_err();
+        assert_eq!(err.pos(), 3);
+        assert_eq!(err.kind(), FuriParseErrorKind::ReadingMismatch);
+        assert_eq!(Furigana::try_from(invalid.to_string()).unwrap_err(), err);
+
+        // Unchecked construction is still possible.
+        assert_eq!(Furigana::new_unchecked(invalid).raw(), invalid);
+    }
+
+    #[test_case("[音楽|おん|がく]が[好|す]き", 2, 2; "mixed")]
+    #[test_case("[音楽|おんがく]", 1, 0; "kanji only")]
+    #[test_case("おんがく", 0, 1; "kana only")]
+    #[test_case("", 0, 0; "empty")]
+    fn test_segment_type_count(furi: &str, kanji: usize, kana: usize) {
+        let furi = Furigana(furi);
+        assert_eq!(furi.kanji_segment_count(), kanji);
+        assert_eq!(furi.kana_segment_count(), kana);
+        assert_eq!(kanji + kana, furi.segment_count());
+    }
+
+    #[test_case("[音楽|おん|がく]が[大好|だい|す]き", ReadingStyle::Detailed; "detailed")]
+    #[test_case("[音楽|おんがく]が[大好|だいす]き", ReadingStyle::Merged; "merged")]
+    #[test_case("[音楽|おん|がく]が[大好|だいす]き", ReadingStyle::Mixed; "mixed")]
+    #[test_case("[音楽|おんがく]が[好|す]き", ReadingStyle::Merged; "single literal ignored")]
+    #[test_case("[好|す]き", ReadingStyle::Detailed; "single literal only")]
+    #[test_case("おんがく", ReadingStyle::Detailed; "kana only")]
+    fn test_reading_style(furi: &str, exp: ReadingStyle) {
+        assert_eq!(Furigana(furi).reading_style(), exp);
+    }
+
+    #[test_case(0, Some('音'))]
+    #[test_case(1, Some('楽'))]
+    #[test_case(2, Some('好'))]
+    #[test_case(3, None; "out of range")]
+    fn test_kanji_char_at(n: usize, exp: Option<char>) {
+        let furi = Furigana("[音楽|おん|がく]が[好|す]きです");
+        assert_eq!(furi.kanji_char_at(n), exp);
+    }
+
+    #[test]
+    fn test_as_plain_kanji() {
+        let furi = Furigana("おんがくがすきです");
+        assert!(matches!(
+            furi.as_plain_kanji(),
+            Cow::Borrowed("おんがくがすきです")
+        ));
+
+        let furi = Furigana("[音楽|おん|がく]が[好|す]きです");
+        let plain = furi.as_plain_kanji();
+        assert!(matches!(plain, Cow::Owned(_)));
+        assert_eq!(plain, "音楽が好きです");
+
+        assert!(matches!(Furigana("").as_plain_kanji(), Cow::Borrowed("")));
+    }
+
+    #[test_case("[音楽|おんがく]が[好|す]きです。", "[音楽|おんがく]が[好|す]きです", "。"; "period")]
+    #[test_case("[本当|ほんとう]？！", "[本当|ほんとう]", "？！"; "only punctuation")]
+    #[test_case("[音楽|おんがく]が[好|す]き", "[音楽|おんがく]が[好|す]き", ""; "no punctuation")]
+    #[test_case("[音楽|おんがく]", "[音楽|おんがく]", ""; "kanji at end")]
+    #[test_case("", "", ""; "empty")]
+    fn test_split_trailing_punct(furi: &str, exp: &str, punct: &str) {
+        assert_eq!(
+            Furigana(furi).split_trailing_punct(),
+            (Furigana(exp), punct)
+        );
+    }
+
+    #[test_case("[音楽|おんがく]が[好|す]き", "[音楽|おん|がく]が[好|す]き", "[音楽|おん|がく]が[好|す]き"; "detailed")]
+    #[test_case("[音楽|おん|がく]が[大好|だいす]き", "[音楽|おんがく]が[大好|だい|す]き", "[音楽|おん|がく]が[大好|だい|す]き"; "both")]
+    #[test_case("[音楽|]が[好|す]き", "[音楽|おんがく]が[好|す]き", "[音楽|おんがく]が[好|す]き"; "empty reading")]
+    #[test_case("おんがく", "おんがく", "おんがく"; "kana")]
+    fn test_merge_with(a: &str, b: &str, exp: &str) {
+        assert_eq!(
+            Furigana(a).merge_with(&Furigana(b)),
+            Ok(Furigana(exp.to_string()))
+        );
+        assert_eq!(
+            Furigana(b).merge_with(&Furigana(a)),
+            Ok(Furigana(exp.to_string()))
+        );
+    }
+
+    #[test_case("[音楽|おんがく]が[好|す]き", "[音楽|おんがく]が[嫌|きら]い"; "different text")]
+    #[test_case("[音楽|おんがく]が", "[音楽|おん|かく]が"; "different reading")]
+    #[test_case("[音楽|おんがく]", "[音楽|おんがく]が"; "different length")]
+    fn test_merge_with_fail(a: &str, b: &str) {
+        assert_eq!(Furigana(a).merge_with(&Furigana(b)), Err(()));
+        assert_eq!(Furigana(b).merge_with(&Furigana(a)), Err(()));
+    }
+
+    #[test_case(
+        "[音楽|おんがく]が[好|す]きです。[本|ほん]も[好|す]き！",
+        &["[音楽|おんがく]が[好|す]きです。", "[本|ほん]も[好|す]き！"];
+        "two sentences"
+    )]
+    #[test_case("[本当|ほんとう]？！[嘘|うそ]", &["[本当|ほんとう]？！", "[嘘|うそ]"]; "no end")]
+    #[test_case("[音楽|おんがく]", &["[音楽|おんがく]"]; "single")]
+    #[test_case("", &[]; "empty")]
+    fn test_sentences(furi: &str, exp: &[&str]) {
+        let furi = Furigana(furi);
+        let sentences: Vec<_> = furi.sentences().collect();
+        assert_eq!(
+            sentences,
+            exp.iter().map(|i| Furigana(*i)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test_case("おんがくがすきです", true; "kana")]
+    #[test_case("[音楽|おんがく]がすき", false; "mixed")]
+    #[test_case("[音楽|おんがく]", false; "kanji")]
+    #[test_case("", true; "empty")]
+    fn test_is_kana_only(furi: &str, exp: bool) {
+        let furi = Furigana(furi);
+        assert_eq!(furi.is_kana_only(), exp);
+        assert_eq!(furi.is_kana_only(), !furi.has_kanji());
+    }
+
+    #[test_case("[音楽|おん|がく]が[大好|だいす]きな[歌|うた]", (2, 1, 2); "mixed")]
+    #[test_case("[音楽|おんがく]", (0, 1, 0); "merged")]
+    #[test_case("[|おん]がく", (0, 0, 2); "reading only")]
+    #[test_case("", (0, 0, 0); "empty")]
+    fn test_style_histogram(furi: &str, exp: (usize, usize, usize)) {
+        let furi = Furigana(furi);
+        let histogram = furi.style_histogram();
+        assert_eq!(histogram, exp);
+        assert_eq!(
+            histogram.0 + histogram.1 + histogram.2,
+            furi.segment_count()
+        );
+    }
+
+    #[test]
+    fn test_try_segments() {
+        let furi = Furigana("[音楽|おん|がく]が[好|す]き");
+        assert_eq!(
+            furi.try_segments(),
+            Ok(vec![
+                SegmentRef::new_kanji("音楽", &["おん", "がく"]),
+                SegmentRef::new_kana("が"),
+                SegmentRef::new_kanji("好", &["す"]),
+                SegmentRef::new_kana("き"),
+            ])
+        );
+
+        let err = Furigana("が[音楽|お|ん|がく]").try_segments().unwrap_err();
+        assert_eq!(err.pos(), 3);
+        assert_eq!(err.kind(), FuriParseErrorKind::ReadingMismatch);
+    }
+
+    #[test]
+    fn test_cow() {
+        let src = "[音楽|おん|がく]が[好|す]き";
+
+        let borrowed = Furigana::from_cow(Cow::Borrowed(src));
+        assert!(matches!(borrowed.0, Cow::Borrowed(_)));
+        assert_eq!(borrowed.kana_str(), "おんがくがすき");
+
+        let owned = Furigana::from_cow(Cow::Owned(src.to_string()));
+        assert!(matches!(owned.0, Cow::Owned(_)));
+        assert_eq!(owned, borrowed);
+
+        let from_str: Furigana<Cow<str>> = Furigana(src).into();
+        assert!(matches!(from_str.0, Cow::Borrowed(_)));
+        let from_string: Furigana<Cow<str>> = Furigana(src.to_string()).into();
+        assert!(matches!(from_string.0, Cow::Owned(_)));
+
+        assert_eq!(borrowed.into_owned(), Furigana(src.to_string()));
+        assert_eq!(owned.to_borrowed(), Furigana(src));
+        assert_eq!(Furigana(src.to_string()).to_borrowed().raw(), src);
+    }
+
+    #[test_case("[音楽|おん|がく]が[好|す]き", '音', Some("おん"); "detailed")]
+    #[test_case("[音楽|おん|がく]が[好|す]き", '好', Some("す"); "second block")]
+    #[test_case("[音楽|おんがく]が[好|す]き", '楽', Some("おんがく"); "merged")]
+    #[test_case("[音楽|おん|がく]が[好|す]き", '日', None; "missing")]
+    #[test_case("[音楽|おん|がく]が[好|す]き", 'が', None; "kana")]
+    fn test_reading_of_kanji(furi: &str, kanji: char, exp: Option<&str>) {
+        assert_eq!(
+            Furigana(furi).reading_of_kanji(kanji),
+            exp.map(|i| i.to_string())
+        );
+    }
+
+    #[test_case("[音楽|おん|がく]が[好|す]き", false, "<ruby>音楽<rt>おんがく</rt></ruby>が<ruby>好<rt>す</rt></ruby>き"; "detailed")]
+    #[test_case("[A|]と[B|び]", false, "Aと<ruby>B<rt>び</rt></ruby>"; "empty plain")]
+    #[test_case("[A|]と[B|び]", true, "<ruby>A<rt></rt></ruby>と<ruby>B<rt>び</rt></ruby>"; "empty rt")]
+    #[test_case("a<b&[音|おと]", false, "a&lt;b&amp;<ruby>音<rt>おと</rt></ruby>"; "escaped kana")]
+    #[test_case("[\"A\"|]>", true, "<ruby>&quot;A&quot;<rt></rt></ruby>&gt;"; "escaped literal")]
+    fn test_to_ruby_html(furi: &str, empty_rt: bool, exp: &str) {
+        let furi = Furigana(furi);
+        assert_eq!(furi.to_ruby_html_with(empty_rt), exp);
+        if !empty_rt {
+            assert_eq!(furi.to_ruby_html(), exp);
+        }
+    }
+
+    #[test]
+    fn test_push_reading() {
+        let mut furi = Furigana(String::new());
+        furi.push_reading(("おんがく", "音楽"));
+        furi.push_reading("が");
+        assert_eq!(furi, "[音楽|おんがく]が");
+
+        assert_eq!(furi.try_push_reading(("す", "好")), Ok(()));
+        assert_eq!(furi.try_push_reading("き"), Ok(()));
+        assert_eq!(furi, "[音楽|おんがく]が[好|す]き");
+    }
+
+    #[test_case(("音楽", "音楽"); "kanji kana")]
+    #[test_case(("おん楽", "音楽"); "mixed kana")]
+    #[test_case(("", "音楽"); "empty kana")]
+    #[test_case(("おんがく", ""); "empty kanji")]
+    #[test_case(("おんがく", "[音楽]"); "brackets")]
+    fn test_try_push_reading_invalid(reading: (&str, &str)) {
+        let mut furi = Furigana(String::from("が"));
+        assert_eq!(furi.try_push_reading(reading), Err(()));
+        assert_eq!(furi, "が");
+    }
+
     #[test]
     fn test_is_empty() {
         assert!(Furigana("").is_empty())
@@ -0,0 +1,59 @@
+use std::{error::Error, fmt::Display};
+
+/// Error returned when encoded furigana can't be parsed. Holds the byte position of the malformed
+/// block within the parsed string and the reason why parsing failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FuriParseError {
+    pos: usize,
+    kind: FuriParseErrorKind,
+}
+
+/// The reason why a furigana block couldn't be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FuriParseErrorKind {
+    /// The kanji block has no reading at all, eg. `[音楽]`.
+    MissingReading,
+
+    /// The kanji block has more than one reading but the amount of readings doesn't match the
+    /// amount of kanji literals, eg. `[音楽|お|ん|がく]`.
+    ReadingMismatch,
+}
+
+impl FuriParseError {
+    /// Create a new parse error at the given byte position.
+    #[inline]
+    pub fn new(pos: usize, kind: FuriParseErrorKind) -> Self {
+        Self { pos, kind }
+    }
+
+    /// Returns the byte position of the block that couldn't be parsed.
+    #[inline]
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the reason why parsing failed.
+    #[inline]
+    pub fn kind(&self) -> FuriParseErrorKind {
+        self.kind
+    }
+}
+
+impl Display for FuriParseErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FuriParseErrorKind::MissingReading => write!(f, "kanji block without reading"),
+            FuriParseErrorKind::ReadingMismatch => {
+                write!(f, "reading count doesn't match kanji literals")
+            }
+        }
+    }
+}
+
+impl Display for FuriParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid furigana at byte {}: {}", self.pos, self.kind)
+    }
+}
+
+impl Error for FuriParseError {}
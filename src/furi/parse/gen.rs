@@ -1,4 +1,4 @@
-use std::str::MatchIndices;
+use std::{ops::Range, str::MatchIndices};
 
 /// Generic parser for furigana segments that only returns the parts as strings.
 pub struct FuriParserGen<'a> {
@@ -10,6 +10,10 @@ pub struct FuriParserGen<'a> {
     kana_start: usize,
     block_start: Option<usize>,
     buf: Option<(&'a str, bool)>,
+
+    // End of the part that hasn't been consumed by `next_back` yet.
+    end: usize,
+    back_buf: Option<(&'a str, bool)>,
 }
 
 impl<'a> FuriParserGen<'a> {
@@ -22,17 +26,36 @@ impl<'a> FuriParserGen<'a> {
             block_start: None,
             buf: None,
             iter: str.match_indices(['[', ']']),
+            end: str.len(),
+            back_buf: None,
         }
     }
 
+    /// Returns the byte offset of `sub` within the parsed string. `sub` has to be a slice returned
+    /// by this parser.
+    #[inline]
+    pub(crate) fn offset_of(&self, sub: &str) -> usize {
+        sub.as_ptr() as usize - self.str.as_ptr() as usize
+    }
+
+    /// Returns an iterator over all parts along with their byte range in the parsed string.
+    pub fn with_offsets(self) -> impl Iterator<Item = (Range<usize>, &'a str, bool)> {
+        let start = self.str.as_ptr() as usize;
+        self.map(move |(part, kanji)| {
+            let offset = part.as_ptr() as usize - start;
+            (offset..offset + part.len(), part, kanji)
+        })
+    }
+
     fn advance(&mut self) -> Option<(&'a str, bool)> {
         loop {
-            let (cur_bracket, c) = match self.iter.next() {
+            // Brackets after `end` have already been consumed from the back.
+            let (cur_bracket, c) = match self.iter.next().filter(|i| i.0 < self.end) {
                 Some(k) => k,
                 None => {
-                    if self.kana_start < self.str.len() {
-                        let kana_text = &self.str[self.kana_start..];
-                        self.kana_start = self.str.len();
+                    if self.kana_start < self.end {
+                        let kana_text = &self.str[self.kana_start..self.end];
+                        self.kana_start = self.end;
                         return Some((kana_text, false));
                     }
 
@@ -63,6 +86,42 @@ impl<'a> FuriParserGen<'a> {
             return to_return;
         }
     }
+
+    fn advance_back(&mut self) -> Option<(&'a str, bool)> {
+        let region = &self.str[self.kana_start..self.end];
+        if region.is_empty() {
+            return None;
+        }
+
+        // A block is a ']' bracket whose previous bracket is a '[' bracket. The bracket before the
+        // region is always a ']' so it can't start a block within the region.
+        let mut search_end = region.len();
+        while let Some(close) = region[..search_end].rfind(']') {
+            let Some(open) = region[..close].rfind(['[', ']']) else {
+                break;
+            };
+
+            if region.as_bytes()[open] == b']' {
+                search_end = open + 1;
+                continue;
+            }
+
+            let kanji = &region[open..close + 1];
+            let mut to_return = Some((kanji, kanji.contains('|')));
+
+            let block_end = self.kana_start + close + 1;
+            if block_end < self.end {
+                self.back_buf = to_return.take();
+                to_return = Some((&self.str[block_end..self.end], false));
+            }
+
+            self.end = self.kana_start + open;
+            return to_return;
+        }
+
+        self.end = self.kana_start;
+        Some((region, false))
+    }
 }
 
 impl<'a> Iterator for FuriParserGen<'a> {
@@ -73,6 +132,15 @@ impl<'a> Iterator for FuriParserGen<'a> {
         if let Some(t) = self.buf.take() {
             return Some(t);
         }
-        self.advance()
+        self.advance().or_else(|| self.back_buf.take())
+    }
+}
+
+impl<'a> DoubleEndedIterator for FuriParserGen<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if let Some(t) = self.back_buf.take() {
+            return Some(t);
+        }
+        self.advance_back().or_else(|| self.buf.take())
     }
 }
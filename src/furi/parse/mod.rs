@@ -1,13 +1,23 @@
+mod error;
 mod gen;
 pub mod reading;
+pub mod repaired;
 pub mod unchecked;
 
+pub use error::{FuriParseError, FuriParseErrorKind};
 pub use gen::FuriParserGen;
 
-use self::unchecked::UncheckedFuriParser;
+use self::{repaired::RepairedFuriParser, unchecked::UncheckedFuriParser};
 use super::segment::SegmentRef;
 use crate::reading::Reading;
 
+/// Returns an iterator over all segments of the encoded furigana string `s` without doing any
+/// checks. This is equal to [`crate::furi::Furigana::segments`] but doesn't require wrapping `s`.
+#[inline]
+pub fn segments(s: &str) -> UncheckedFuriParser<'_> {
+    FuriParser::new(s).unchecked()
+}
+
 /// Iterator over encoded furigana which returns ReadingPartRef's of all parts.
 /// Encoded furigana format: `[拝金主義|はい|きん|しゅ|ぎ]は[問題|もん|だい]`
 pub struct FuriParser<'a> {
@@ -30,10 +40,17 @@ impl<'a> FuriParser<'a> {
         UncheckedFuriParser::new(self.gen_parser)
     }
 
-    /// Parses a single string segment
+    /// Returns an iterator over all parsed segments which repairs kanji blocks with a reading count
+    /// not matching the kanji literals by merging their readings into a single reading.
+    #[inline]
+    pub fn repaired(self) -> RepairedFuriParser<'a> {
+        RepairedFuriParser::new(self.gen_parser)
+    }
+
+    /// Parses a single string segment. The position of a returned error is relative to `txt`.
     #[inline]
-    pub fn from_seg_str(txt: &'a str, kanji: bool) -> Result<SegmentRef, ()> {
-        SegmentRef::parse_str(txt, kanji, true)
+    pub fn from_seg_str(txt: &'a str, kanji: bool) -> Result<SegmentRef, FuriParseError> {
+        SegmentRef::parse_str(txt, kanji, true).map_err(|kind| FuriParseError::new(0, kind))
     }
 
     /// Returns `true` if the given furigana is parsable.
@@ -41,29 +58,58 @@ impl<'a> FuriParser<'a> {
     where
         S: AsRef<str>,
     {
-        FuriParser::new(s.as_ref()).all(|i| i.is_ok())
+        Self::validate(s).is_ok()
+    }
+
+    /// Checks whether the given furigana is parsable and returns the error of the first invalid
+    /// block if it isn't.
+    pub fn validate<S>(s: S) -> Result<(), FuriParseError>
+    where
+        S: AsRef<str>,
+    {
+        match FuriParser::new(s.as_ref()).find_map(|i| i.err()) {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    /// Validates all given furigana strings and returns the index and first error of each invalid
+    /// item.
+    pub fn validate_all<'b, I>(items: I) -> Vec<(usize, FuriParseError)>
+    where
+        I: IntoIterator<Item = &'b str>,
+    {
+        items
+            .into_iter()
+            .enumerate()
+            .filter_map(|(pos, item)| Self::validate(item).err().map(|e| (pos, e)))
+            .collect()
     }
 
     /// Parses the furigana to a vec of segments.
     #[inline]
-    pub fn to_vec(self) -> Result<Vec<SegmentRef<'a>>, ()> {
+    pub fn to_vec(self) -> Result<Vec<SegmentRef<'a>>, FuriParseError> {
         self.collect()
     }
 
     /// Parses a string to a [`Reading`]. This is slower than the unchecked version as it does
     /// checks and allocates each segment before allocating the reading.
     #[inline]
-    pub fn to_reading(self) -> Result<Reading, ()> {
+    pub fn to_reading(self) -> Result<Reading, FuriParseError> {
         self.collect()
     }
 }
 
 impl<'a> Iterator for FuriParser<'a> {
-    type Item = Result<SegmentRef<'a>, ()>;
+    type Item = Result<SegmentRef<'a>, FuriParseError>;
 
     fn next(&mut self) -> Option<Self::Item> {
         let (txt, kanji) = self.gen_parser.next()?;
-        Some(Self::from_seg_str(txt, kanji))
+        let seg = SegmentRef::parse_str(txt, kanji, true).map_err(|kind| {
+            let pos = self.gen_parser.offset_of(txt);
+            FuriParseError::new(pos, kind)
+        });
+        Some(seg)
     }
 }
 
@@ -76,6 +122,53 @@ mod test {
     use std::str::FromStr;
     use test_case::test_case;
 
+    #[test]
+    fn test_segments() {
+        let segs: Vec<_> = segments("[音楽|おん|がく]が[好|す]き").collect();
+        assert_eq!(
+            segs,
+            vec![
+                SegmentRef::new_kanji("音楽", &["おん", "がく"]),
+                SegmentRef::new_kana("が"),
+                SegmentRef::new_kanji("好", &["す"]),
+                SegmentRef::new_kana("き"),
+            ]
+        );
+        assert!(segments("").next().is_none());
+    }
+
+    #[test]
+    fn test_reading_only_block() {
+        let seg = FuriParser::from_seg_str("[|おん]", true).unwrap();
+        assert_eq!(seg, SegmentRef::Kana("おん"));
+
+        let segs = FuriParser::new("[|おん]がく").to_vec().unwrap();
+        assert_eq!(
+            segs,
+            vec![SegmentRef::Kana("おん"), SegmentRef::Kana("がく")]
+        );
+        assert_eq!(Furigana("[|おん]がく").kana_str(), "おんがく");
+
+        let seg = FuriParser::from_seg_str("[A|]", true).unwrap();
+        assert_eq!(seg, SegmentRef::new_kanji("A", &[""]));
+    }
+
+    #[test_case("おんがくが[好|す]"; "End_kanji")]
+    #[test_case("[音楽|おん|がく]が[好|す]き")]
+    #[test_case("[永遠|えい|えん]にあなたのものです。 [アーメン]"; "block")]
+    #[test_case(""; "empty")]
+    fn test_gen_with_offsets(furi: &str) {
+        let mut rebuilt = String::new();
+        let mut last_end = 0;
+        for (range, part, _) in FuriParserGen::new(furi).with_offsets() {
+            assert_eq!(range.start, last_end);
+            assert_eq!(&furi[range.clone()], part);
+            rebuilt.push_str(&furi[range.clone()]);
+            last_end = range.end;
+        }
+        assert_eq!(rebuilt, furi);
+    }
+
     #[test_case("おんがくが[好|す]"; "End_kanji")]
     #[test_case("おんがくが[好|す]きです")]
     #[test_case("[音楽|おん|がく]が[好|す]き")]
@@ -110,6 +203,41 @@ mod test {
         assert_eq!(furigana.to_reading(), reading);
     }
 
+    #[test_case("[音楽|お|ん|がく]", 0, FuriParseErrorKind::ReadingMismatch; "start")]
+    #[test_case("それは[音楽|お|ん|がく]です", 9, FuriParseErrorKind::ReadingMismatch; "after kana")]
+    #[test_case("[好|す]き[拝金主義|はい|]", 12, FuriParseErrorKind::ReadingMismatch; "after kanji")]
+    fn test_parse_error_pos(furi: &str, pos: usize, kind: FuriParseErrorKind) {
+        let err = FuriParser::new(furi).to_vec().unwrap_err();
+        assert_eq!(err, FuriParseError::new(pos, kind));
+        assert!(furi[err.pos()..].starts_with('['));
+    }
+
+    #[test]
+    fn test_validate_all() {
+        let lines = [
+            "[音楽|おん|がく]が[好|す]き",
+            "[音楽|お|ん|がく]",
+            "",
+            "この[人|ひと]が[嫌|きら]いです。",
+            "それは[拝金主義|はい|]",
+        ];
+        let errors = FuriParser::validate_all(lines);
+        assert_eq!(
+            errors,
+            vec![
+                (
+                    1,
+                    FuriParseError::new(0, FuriParseErrorKind::ReadingMismatch)
+                ),
+                (
+                    4,
+                    FuriParseError::new(9, FuriParseErrorKind::ReadingMismatch)
+                ),
+            ]
+        );
+        assert!(FuriParser::validate_all(lines.iter().take(1).copied()).is_empty());
+    }
+
     #[test]
     fn test_empty() {
         let e = Segment::from_str("").unwrap();
@@ -7,6 +7,7 @@ pub struct FuriToReadingParser<'a> {
     str: &'a str,
     to_kana: bool,
     kanji_fallback: bool,
+    trim: bool,
 }
 
 impl<'a> FuriToReadingParser<'a> {
@@ -17,6 +18,7 @@ impl<'a> FuriToReadingParser<'a> {
             str,
             to_kana,
             kanji_fallback: true,
+            trim: false,
         }
     }
 
@@ -27,6 +29,13 @@ impl<'a> FuriToReadingParser<'a> {
         self
     }
 
+    /// Sets whether leading and trailing whitespace of the input should be ignored. Defaults to
+    /// `false`.
+    pub fn trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+
     /// Returns `true` if the parser would return an empty string.
     #[inline]
     pub fn is_empty(&self) -> bool {
@@ -35,7 +44,7 @@ impl<'a> FuriToReadingParser<'a> {
 
     /// Returns the count of chars of the string that would be returned when parsing.
     pub fn char_count(&self) -> usize {
-        if self.str.is_empty() {
+        if self.src().is_empty() {
             return 0;
         }
         let mut len = 0;
@@ -47,7 +56,7 @@ impl<'a> FuriToReadingParser<'a> {
     /// function is slow, so try caching if possible. However its faster than parsing and checking
     /// length as it doesn't allocate any string.
     pub fn len(&self) -> usize {
-        if self.str.is_empty() {
+        if self.src().is_empty() {
             return 0;
         }
         let mut len = 0;
@@ -55,9 +64,16 @@ impl<'a> FuriToReadingParser<'a> {
         len
     }
 
+    /// Returns `true` if parsing would return a string equal to `other`. This doesn't allocate.
+    pub fn eq_str(&self, other: &str) -> bool {
+        let mut rest = Some(other);
+        self.run(|s| rest = rest.and_then(|r| r.strip_prefix(s)));
+        rest.is_some_and(|r| r.is_empty())
+    }
+
     /// Parses the furigana to either kana or kanji.
     pub fn parse(&self) -> String {
-        let mut buf = String::with_capacity(self.str.len().saturating_sub(10));
+        let mut buf = String::with_capacity(self.src().len().saturating_sub(10));
         self.run(|s| buf.push_str(s));
         buf
     }
@@ -116,12 +132,22 @@ impl<'a> FuriToReadingParser<'a> {
         (kana_buf, has_kanji.then_some(kanji_buf))
     }
 
+    /// Returns the input string the parser works on.
+    #[inline]
+    fn src(&self) -> &'a str {
+        if self.trim {
+            self.str.trim()
+        } else {
+            self.str
+        }
+    }
+
     /// Runs the parser and writes all sub strings into `w`.
     fn run<W>(&self, mut w: W)
     where
         W: FnMut(&str),
     {
-        for (txt, kanji) in FuriParserGen::new(self.str) {
+        for (txt, kanji) in FuriParserGen::new(self.src()) {
             if kanji {
                 self.accept_kanji(txt, &mut w);
             } else {
@@ -229,6 +255,24 @@ mod test {
         assert_eq!(parsed, out);
     }
 
+    #[test_case("[音楽|おん|がく]が[好|す]き", "おんがくがすき", true)]
+    #[test_case("[音楽|おん|がく]が[好|す]き", "おんがくがす", false; "prefix")]
+    #[test_case("[音楽|おん|がく]が[好|す]き", "おんがくがすきだ", false; "longer")]
+    #[test_case("", "", true; "empty")]
+    fn test_eq_str(furi: &str, other: &str, exp: bool) {
+        assert_eq!(FuriToReadingParser::new(furi, true).eq_str(other), exp);
+    }
+
+    #[test_case(" [音楽|おんがく] ", false, " おんがく "; "no trim")]
+    #[test_case(" [音楽|おんがく] ", true, "おんがく"; "trim")]
+    #[test_case("\t[音楽|おんがく]が[好|す]き\n", true, "おんがくがすき"; "trim tab newline")]
+    fn test_trim(furi: &str, trim: bool, exp: &str) {
+        let parser = FuriToReadingParser::new(furi, true).trim(trim);
+        assert_eq!(parser.parse(), exp);
+        assert_eq!(parser.len(), exp.len());
+        assert!(parser.eq_str(exp));
+    }
+
     #[test]
     fn test_empty_kanji_block() {
         let s =
@@ -0,0 +1,83 @@
+use super::gen::FuriParserGen;
+use super::unchecked::UncheckedFuriParser;
+use crate::furi::segment::{kanji::as_kanji::AsKanjiSegment, AsSegment, Segment};
+
+/// Iterator over encoded furigana which repairs malformed kanji blocks instead of failing.
+/// Kanji blocks whose reading count doesn't match the amount of kanji literals get all readings
+/// merged into a single reading, eg. `[音楽|お|ん|がく]` => `[音楽|おんがく]`.
+pub struct RepairedFuriParser<'a> {
+    gen_parser: FuriParserGen<'a>,
+}
+
+impl<'a> RepairedFuriParser<'a> {
+    /// Creates a new furigana parser for the given string.
+    #[inline]
+    pub(super) fn new(gen_parser: FuriParserGen<'a>) -> Self {
+        Self { gen_parser }
+    }
+
+    /// Parses the furigana to a vec of segments.
+    #[inline]
+    pub fn to_vec(self) -> Vec<Segment> {
+        self.collect()
+    }
+}
+
+impl<'a> Iterator for RepairedFuriParser<'a> {
+    type Item = Segment;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (txt, kanji) = self.gen_parser.next()?;
+        let seg = UncheckedFuriParser::from_seg_str(txt, kanji);
+
+        let Some(kanji) = seg.as_kanji() else {
+            return Some(seg.to_owned());
+        };
+
+        let readings = kanji.readings();
+        if kanji.is_detailed() || readings.len() <= 1 {
+            return Some(seg.to_owned());
+        }
+
+        let reading = readings.concat();
+        Some(Segment::new_kanji(kanji.literals().to_string(), &[reading]))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::furi::{
+        parse::FuriParser,
+        segment::{kanji::as_kanji::AsKanjiSegment, AsSegment, Segment},
+        Furigana,
+    };
+    use test_case::test_case;
+
+    #[test_case("[音楽|お|ん|がく]が[好|す]き", "[音楽|おんがく]が[好|す]き"; "too many readings")]
+    #[test_case("[拝金主義|はい|きん|しゅ]", "[拝金主義|はいきんしゅ]"; "too few readings")]
+    #[test_case("[音楽|おん|がく]が[好|す]き", "[音楽|おん|がく]が[好|す]き"; "valid")]
+    #[test_case("[6|][時|じ]", "[6|][時|じ]"; "empty reading")]
+    fn test_repaired(furi: &str, exp: &str) {
+        assert!(!FuriParser::check(furi) || furi == exp);
+
+        let repaired = FuriParser::new(furi).repaired().to_vec();
+        assert!(repaired
+            .iter()
+            .filter_map(|i| i.as_kanji())
+            .all(|k| k.is_valid()));
+        assert_eq!(Furigana::from_iter(repaired.iter()), Furigana(exp));
+        assert!(FuriParser::check(exp));
+    }
+
+    #[test]
+    fn test_repaired_segment() {
+        let seg = FuriParser::new("[音楽|お|ん|がく]").repaired().next();
+        assert_eq!(
+            seg,
+            Some(Segment::new_kanji(
+                "音楽".to_string(),
+                &["おんがく".to_string()]
+            ))
+        );
+    }
+}
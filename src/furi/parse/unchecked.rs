@@ -46,11 +46,68 @@ impl<'a> Iterator for UncheckedFuriParser<'a> {
     }
 }
 
+impl<'a> DoubleEndedIterator for UncheckedFuriParser<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.gen_parser
+            .next_back()
+            .map(|(txt, kanji)| Self::from_seg_str(txt, kanji))
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::furi::parse::FuriParser;
+    use crate::furi::{
+        parse::{FuriParseErrorKind, FuriParser, FuriParserGen},
+        Furigana,
+    };
     use test_case::test_case;
 
+    #[test_case("[音楽|おん|がく]が[好|す]き")]
+    #[test_case("おんがくが[好|す]"; "End_kanji")]
+    #[test_case("[音楽|おん|がく]"; "single")]
+    #[test_case("かな"; "kana only")]
+    #[test_case("[[1|],[2|]]のとき、[f|えふ]"; "with brackets")]
+    #[test_case("[永遠|えい|えん]にあなたのものです。 [アーメン]"; "block")]
+    #[test_case("]が[好|す]][き"; "stray brackets")]
+    #[test_case(""; "empty")]
+    fn test_next_back(furi: &str) {
+        let furi = Furigana(furi);
+        assert_eq!(
+            furi.segments().next_back().as_ref(),
+            furi.as_segments_ref().last()
+        );
+
+        let forward: Vec<_> = furi.gen_parser().collect();
+        let mut backward: Vec<_> = furi.gen_parser().rev().collect();
+        backward.reverse();
+        assert_eq!(forward, backward);
+
+        // Alternate between both ends.
+        for split in 0..=forward.len() {
+            let mut parser = furi.gen_parser();
+            let mut front: Vec<_> = parser.by_ref().take(split).collect();
+            let back: Vec<_> = parser.rev().collect();
+            front.extend(back.into_iter().rev());
+            assert_eq!(front, forward);
+        }
+
+        let mut parser = furi.gen_parser();
+        let mut mixed = vec![];
+        let mut from_back = vec![];
+        loop {
+            match (parser.next(), parser.next_back()) {
+                (None, None) => break,
+                (f, b) => {
+                    mixed.extend(f);
+                    from_back.extend(b);
+                }
+            }
+        }
+        mixed.extend(from_back.into_iter().rev());
+        assert_eq!(mixed, forward);
+        assert_eq!(FuriParserGen::new(furi.raw()).rev().count(), forward.len());
+    }
+
     #[test_case("[おんがく]"; "single kana in kanji brackets")]
     #[test_case("[おんがく|]";"Kana in kanji with space")]
     #[test_case("[音楽]";"kanji")]
@@ -69,6 +126,9 @@ mod test {
     #[test_case("[拝金主義|はい|]")]
     fn test_parse_furigana_error(furi: &str) {
         let parsed = FuriParser::new(furi).collect::<Result<Vec<_>, _>>();
-        assert_eq!(parsed, Err(()));
+        assert_eq!(
+            parsed.map_err(|i| i.kind()),
+            Err(FuriParseErrorKind::ReadingMismatch)
+        );
     }
 }
@@ -1,5 +1,9 @@
 use super::{kanji::as_kanji::AsKanjiSegment, AsSegment};
 use crate::reading::traits::AsReadingRef;
+use std::{
+    convert::Infallible,
+    io::{self, Write},
+};
 
 /// An encoder fur furigana.
 pub struct FuriEncoder<'a> {
@@ -14,12 +18,9 @@ impl<'a> FuriEncoder<'a> {
     }
 
     /// Encodes a segment
+    #[inline]
     pub fn write_seg<S: AsSegment>(&mut self, segment: S) {
-        if let Some(kanji) = segment.as_kanji() {
-            self.write_kanji(kanji);
-        } else if let Some(kana) = segment.as_kana() {
-            self.write_kana(kana.as_ref());
-        }
+        let Ok(()) = encode_seg(self.out, segment);
     }
 
     /// Writes kana to the buffer.
@@ -29,43 +30,23 @@ impl<'a> FuriEncoder<'a> {
     }
 
     /// Writes a single block of `[kanji|kana]` to the buffer.
+    #[inline]
     pub fn write_block(&mut self, kanji: &str, kana: &str) {
-        self.out.push('[');
-        self.out.push_str(kanji);
-        self.out.push('|');
-        self.out.push_str(kana);
-        self.out.push(']');
+        let Ok(()) = encode_block(self.out, kanji, kana);
     }
 
     /// Writes a [`jp_utils::reading::Reading`] into the furi encoder.
     ///
     /// Note that `readings` can contain kana characters in their kanji strings.
+    #[inline]
     pub fn write_reading<R: AsReadingRef>(&mut self, r: R) {
-        let r = r.as_reading_ref();
-        if let Some(kanji) = r.kanji() {
-            self.write_block(kanji, r.kana());
-        } else {
-            self.write_kana(r.kana());
-        }
+        let Ok(()) = encode_reading(self.out, r);
     }
 
     /// Writes a kanji segment
+    #[inline]
     pub fn write_kanji<K: AsKanjiSegment>(&mut self, k: K) {
-        let readings = k.readings();
-        let detailed = k.is_detailed();
-
-        self.out.push('[');
-        self.out.push_str(k.literals().as_ref());
-        self.out.push('|');
-
-        for (pos, reading) in readings.iter().enumerate() {
-            if pos > 0 && detailed {
-                self.out.push('|');
-            }
-            self.out.push_str(reading.as_ref());
-        }
-
-        self.out.push(']');
+        let Ok(()) = encode_kanji(self.out, k);
     }
 }
 
@@ -84,9 +65,151 @@ where
     }
 }
 
+/// A furigana encoder that writes the encoded segments directly into an [`io::Write`] without
+/// buffering the whole result.
+pub struct FuriWriter<W> {
+    out: W,
+}
+
+impl<W: Write> FuriWriter<W> {
+    /// Create a new furigana writer with `out` as output.
+    #[inline]
+    pub fn new(out: W) -> Self {
+        Self { out }
+    }
+
+    /// Encodes a segment
+    #[inline]
+    pub fn write_seg<S: AsSegment>(&mut self, segment: S) -> io::Result<()> {
+        encode_seg(&mut IoSink(&mut self.out), segment)
+    }
+
+    /// Encodes all segments of `iter`.
+    pub fn write_all<I, S>(&mut self, iter: I) -> io::Result<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsSegment,
+    {
+        for seg in iter {
+            self.write_seg(seg)?;
+        }
+        Ok(())
+    }
+
+    /// Writes kana to the output.
+    #[inline]
+    pub fn write_kana(&mut self, kana: &str) -> io::Result<()> {
+        self.out.write_all(kana.as_bytes())
+    }
+
+    /// Writes a single block of `[kanji|kana]` to the output.
+    #[inline]
+    pub fn write_block(&mut self, kanji: &str, kana: &str) -> io::Result<()> {
+        encode_block(&mut IoSink(&mut self.out), kanji, kana)
+    }
+
+    /// Writes a [`jp_utils::reading::Reading`] to the output.
+    #[inline]
+    pub fn write_reading<R: AsReadingRef>(&mut self, r: R) -> io::Result<()> {
+        encode_reading(&mut IoSink(&mut self.out), r)
+    }
+
+    /// Writes a kanji segment
+    #[inline]
+    pub fn write_kanji<K: AsKanjiSegment>(&mut self, k: K) -> io::Result<()> {
+        encode_kanji(&mut IoSink(&mut self.out), k)
+    }
+
+    /// Flushes the underlying output.
+    #[inline]
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+
+    /// Returns the underlying output.
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+/// Output of the furigana encoding functions.
+trait EncodeSink {
+    type Error;
+
+    fn put(&mut self, s: &str) -> Result<(), Self::Error>;
+}
+
+impl EncodeSink for String {
+    type Error = Infallible;
+
+    #[inline]
+    fn put(&mut self, s: &str) -> Result<(), Infallible> {
+        self.push_str(s);
+        Ok(())
+    }
+}
+
+/// Adapter to use an [`io::Write`] as [`EncodeSink`].
+struct IoSink<'a, W>(&'a mut W);
+
+impl<W: Write> EncodeSink for IoSink<'_, W> {
+    type Error = io::Error;
+
+    #[inline]
+    fn put(&mut self, s: &str) -> io::Result<()> {
+        self.0.write_all(s.as_bytes())
+    }
+}
+
+fn encode_seg<O: EncodeSink, S: AsSegment>(out: &mut O, segment: S) -> Result<(), O::Error> {
+    if let Some(kanji) = segment.as_kanji() {
+        encode_kanji(out, kanji)
+    } else if let Some(kana) = segment.as_kana() {
+        out.put(kana.as_ref())
+    } else {
+        Ok(())
+    }
+}
+
+fn encode_block<O: EncodeSink>(out: &mut O, kanji: &str, kana: &str) -> Result<(), O::Error> {
+    out.put("[")?;
+    out.put(kanji)?;
+    out.put("|")?;
+    out.put(kana)?;
+    out.put("]")
+}
+
+fn encode_reading<O: EncodeSink, R: AsReadingRef>(out: &mut O, r: R) -> Result<(), O::Error> {
+    let r = r.as_reading_ref();
+    if let Some(kanji) = r.kanji() {
+        encode_block(out, kanji, r.kana())
+    } else {
+        out.put(r.kana())
+    }
+}
+
+fn encode_kanji<O: EncodeSink, K: AsKanjiSegment>(out: &mut O, k: K) -> Result<(), O::Error> {
+    let readings = k.readings();
+    let detailed = k.is_detailed();
+
+    out.put("[")?;
+    out.put(k.literals().as_ref())?;
+    out.put("|")?;
+
+    for (pos, reading) in readings.iter().enumerate() {
+        if pos > 0 && detailed {
+            out.put("|")?;
+        }
+        out.put(reading.as_ref())?;
+    }
+
+    out.put("]")
+}
+
 #[cfg(test)]
 mod test {
-    use crate::furi::Furigana;
+    use crate::furi::{segment::SegmentRef, Furigana};
 
     use super::*;
     use test_case::test_case;
@@ -115,4 +238,41 @@ mod test {
         encoder.extend(&Furigana(furi));
         assert_eq!(buf2, furi);
     }
+
+    #[test_case("";"empty")]
+    #[test_case("[音楽|おん|がく]が[好|す]き")]
+    #[test_case("[音楽|おんがく]が[好|す]き"; "single reading")]
+    #[test_case("[2|][x|えっくす]+[1|]の[定義|てい|ぎ]"; "empty reading")]
+    fn test_furi_writer(furi: &str) {
+        let mut writer = FuriWriter::new(Vec::new());
+        writer.write_all(&Furigana(furi)).unwrap();
+        assert_eq!(writer.into_inner(), furi.as_bytes());
+    }
+
+    #[test]
+    fn test_furi_writer_reading() {
+        let mut writer = FuriWriter::new(Vec::new());
+        writer.write_reading(("おんがく", "音楽")).unwrap();
+        writer.write_reading("が").unwrap();
+        assert_eq!(writer.into_inner(), "[音楽|おんがく]が".as_bytes());
+    }
+
+    #[test_case("[音楽|おん|がく]が[好|す]き")]
+    #[test_case("この[人|ひと]が[嫌|きら]いです。")]
+    #[test_case("[2|][x|えっくす]+[1|]の[定義|てい|ぎ][域|いき]が[A|えい]=[[1|],[2|]]のとき、[f|えふ]の[値域|ち|いき]は[f|えふ]([A|えい]) = [[3|],[5|]]となる。"; "with brackets")]
+    fn test_encoded_len_hint_bound(furi: &str) {
+        for seg in &Furigana(furi) {
+            assert!(seg.encoded_len_hint() >= seg.encode().len());
+        }
+    }
+
+    #[test_case("おんがく", 12; "kana")]
+    #[test_case("[音楽|おん|がく]", 22; "detailed")]
+    #[test_case("[音楽|おんがく]", 21; "single reading")]
+    #[test_case("[A|]", 4; "empty reading")]
+    fn test_encoded_len_hint(seg: &str, exp: usize) {
+        let seg = SegmentRef::from_str_unchecked(seg);
+        assert_eq!(seg.encoded_len_hint(), exp);
+        assert_eq!(seg.encode().len(), exp);
+    }
 }
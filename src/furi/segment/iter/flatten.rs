@@ -13,11 +13,27 @@ where
     K: AsKanjiSegment,
 {
     pub fn new<A: AsSegment<KanjiType = K, StrType = S>>(seg: &'a A) -> Self
+    where
+        A::StrType: 'a,
+    {
+        Self::with_grouping(seg, false)
+    }
+
+    /// Creates a new flatten iterator that keeps kanji blocks with multiple literals intact
+    /// instead of splitting them into one segment per literal.
+    pub fn new_grouped<A: AsSegment<KanjiType = K, StrType = S>>(seg: &'a A) -> Self
+    where
+        A::StrType: 'a,
+    {
+        Self::with_grouping(seg, true)
+    }
+
+    fn with_grouping<A: AsSegment<KanjiType = K, StrType = S>>(seg: &'a A, grouped: bool) -> Self
     where
         A::StrType: 'a,
     {
         if let Some(kanji) = seg.as_kanji() {
-            return Self::Kanji(FlattenKanjiIter::new(kanji));
+            return Self::Kanji(FlattenKanjiIter::new(kanji, grouped));
         }
 
         let kana = seg.as_kana().unwrap();
@@ -49,6 +65,7 @@ where
 pub struct FlattenKanjiIter<'a, K> {
     kanji: &'a K,
     is_detailed: bool,
+    grouped: bool,
     chars: Chars<'a>,
     pos: usize,
 }
@@ -58,12 +75,13 @@ where
     K: AsKanjiSegment,
 {
     #[inline]
-    fn new(kanji: &'a K) -> Self {
+    fn new(kanji: &'a K, grouped: bool) -> Self {
         let detailed = kanji.is_detailed();
         let chars = kanji.literals().as_ref().chars();
         Self {
             kanji,
             is_detailed: detailed,
+            grouped,
             chars,
             pos: 0,
         }
@@ -77,6 +95,21 @@ where
     type Item = Segment;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.grouped && self.is_detailed {
+            if self.pos > 0 {
+                return None;
+            }
+            self.pos += 1;
+            let kanji = self.kanji.literals().as_ref().to_string();
+            let readings: Vec<_> = self
+                .kanji
+                .readings()
+                .iter()
+                .map(|i| i.as_ref().to_string())
+                .collect();
+            return Some(Segment::new_kanji(kanji, &readings));
+        }
+
         if !self.is_detailed {
             if self.pos > 0 {
                 return None;
@@ -116,6 +149,23 @@ mod test {
         });
     }
 
+    #[test_case("[音楽|おん|がく]", 2; "detailed")]
+    #[test_case("[大学|だいがく]", 1; "merged")]
+    #[test_case("[好|す]", 1; "single")]
+    #[test_case("おんがく", 1; "kana")]
+    pub fn test_flatten_grouped(p: &str, split_count: usize) {
+        let part = Segment::from_str(p).unwrap();
+
+        let grouped: Vec<_> = FlattenIter::new_grouped(&part).collect();
+        assert_eq!(grouped, vec![part.clone()]);
+        assert_eq!(
+            part.reading_flattened_grouped().collect::<Vec<_>>(),
+            grouped
+        );
+
+        assert_eq!(FlattenIter::new(&part).count(), split_count);
+    }
+
     #[test_case("おんがく", vec![("おんがく")])]
     #[test_case("へんたい", vec![("へんたい")])]
     pub fn test_flatten_kana_iter(p: &str, readings: Vec<&str>) {
@@ -54,6 +54,18 @@ pub trait AsKanjiSegment {
         self.readings().len()
     }
 
+    /// Returns the `i`th reading of the kanji segment.
+    #[inline]
+    fn reading_at(&self, i: usize) -> Option<&Self::StrType> {
+        self.readings().get(i)
+    }
+
+    /// Returns the `i`th kanji literal of the kanji segment.
+    #[inline]
+    fn literal_at(&self, i: usize) -> Option<char> {
+        self.literals().as_ref().chars().nth(i)
+    }
+
     /// Returns an iterator over all kanji literal with its readings assigned.
     #[inline]
     fn literal_readings(&self) -> LitReadingsIter<Self>
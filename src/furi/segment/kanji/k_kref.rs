@@ -76,3 +76,23 @@ impl<'a> PartialEq<Kanji> for &'a KanjiRef<'a> {
         (*self).eq(other)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_reading_literal_at() {
+        let kanji = KanjiRef::new("音楽", &["おん", "がく"]);
+        assert_eq!(kanji.reading_at(0), Some(&"おん"));
+        assert_eq!(kanji.reading_at(1), Some(&"がく"));
+        assert_eq!(kanji.reading_at(2), None);
+        assert_eq!(kanji.literal_at(0), Some('音'));
+        assert_eq!(kanji.literal_at(1), Some('楽'));
+        assert_eq!(kanji.literal_at(2), None);
+
+        let owned = kanji.to_owned();
+        assert_eq!(owned.reading_at(1).map(|i| i.as_str()), Some("がく"));
+        assert_eq!(owned.literal_at(1), Some('楽'));
+    }
+}
@@ -18,6 +18,12 @@ impl Kanji {
         Self { lit, readings }
     }
 
+    /// Returns mutable access to the readings of the kanji.
+    #[inline]
+    pub fn readings_mut(&mut self) -> &mut [String] {
+        self.readings.as_mut_slice()
+    }
+
     #[inline]
     pub fn as_ref(&self) -> KanjiRef {
         let readings = self.readings.iter().map(|i| i.as_str()).collect();
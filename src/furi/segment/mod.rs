@@ -8,3 +8,41 @@ pub mod traits;
 pub use s_owned::Segment;
 pub use s_ref::SegmentRef;
 pub use traits::{AsSegment, AsSegmentRef};
+
+use kanji::as_kanji::AsKanjiSegment;
+use std::{
+    fmt::{self, Formatter},
+    hash::{Hash, Hasher},
+};
+
+/// Hashes a segment independently of its type, so equal [`Segment`] and [`SegmentRef`] values
+/// have the same hash.
+fn hash_segment<S: AsSegment, H: Hasher>(seg: &S, state: &mut H) {
+    match seg.as_kanji() {
+        Some(kanji) => {
+            state.write_u8(1);
+            kanji.literals().as_ref().hash(state);
+            let readings = kanji.readings();
+            state.write_usize(readings.len());
+            for reading in readings {
+                reading.as_ref().hash(state);
+            }
+        }
+        None => {
+            state.write_u8(0);
+            seg.main_reading().as_ref().hash(state);
+        }
+    }
+}
+
+/// Formats a segment in its encoded form, eg. `[音楽|おん|がく]`, or in a human-friendly form like
+/// `音楽(おんがく)` if the alternate flag (`{:#}`) is set.
+fn fmt_segment<S: AsSegment>(seg: &S, f: &mut Formatter<'_>) -> fmt::Result {
+    match seg.as_kanji() {
+        Some(kanji) if f.alternate() => {
+            write!(f, "{}({})", kanji.literals().as_ref(), kanji.full_reading())
+        }
+        Some(_) => f.write_str(&seg.encode()),
+        None => f.write_str(seg.main_reading().as_ref()),
+    }
+}
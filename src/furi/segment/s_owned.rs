@@ -1,12 +1,20 @@
 use super::{
-    kanji::{as_kanji::AsKanjiRef, Kanji},
+    kanji::{
+        as_kanji::{AsKanjiRef, AsKanjiSegment},
+        Kanji,
+    },
     s_ref::SegmentRef,
     traits::{AsSegment, AsSegmentRef},
 };
-use std::str::FromStr;
+use crate::furi::parse::{FuriParseError, FuriParser};
+use std::{
+    fmt::{self, Display, Formatter},
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
 
 /// A single segment of a Furigana formatted string. Either holds a Kana or Kanji part.
-#[derive(Clone, PartialEq, Debug, Eq, Hash)]
+#[derive(Clone, PartialEq, Debug, Eq)]
 #[cfg_attr(feature = "with_serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Segment {
     Kana(String),
@@ -25,6 +33,34 @@ impl Segment {
     pub fn new_kanji(lits: String, readings: &[String]) -> Self {
         Self::Kanji(Kanji::new(lits, readings))
     }
+
+    /// Parses a single encoded segment, eg. `[音楽|おん|がく]` or `おんがく`. Unlike the [`FromStr`]
+    /// implementation this returns the reason why parsing failed.
+    #[inline]
+    pub fn parse(s: &str) -> Result<Segment, FuriParseError> {
+        let kanji = s.starts_with('[') && s.ends_with(']');
+        FuriParser::from_seg_str(s, kanji).map(|i| i.to_owned())
+    }
+
+    /// Returns `false` if the segment is a kanji block whose readings can't possibly belong to its
+    /// literals, eg. `[拝金主義|は]`, as each literal needs at least one kana. Kana segments and
+    /// kanji blocks without a reading like `[A|]` are always plausible.
+    pub fn is_plausible(&self) -> bool {
+        let Segment::Kanji(kanji) = self else {
+            return true;
+        };
+
+        let readings = kanji.readings();
+        if readings.iter().all(|r| r.is_empty()) {
+            return true;
+        }
+
+        if kanji.is_detailed() {
+            readings.iter().all(|r| !r.is_empty())
+        } else {
+            self.reading_width() >= kanji.lit_count()
+        }
+    }
 }
 
 impl<'a> AsSegmentRef<'a> for &'a Segment {
@@ -80,6 +116,20 @@ impl FromStr for Segment {
     }
 }
 
+impl Display for Segment {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        super::fmt_segment(self, f)
+    }
+}
+
+impl Hash for Segment {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        super::hash_segment(self, state);
+    }
+}
+
 impl<'a> PartialEq<SegmentRef<'a>> for Segment {
     fn eq(&self, other: &SegmentRef<'a>) -> bool {
         match (self, other) {
@@ -96,3 +146,40 @@ impl<'a> PartialEq<SegmentRef<'a>> for &'a Segment {
         (*self).eq(other)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::furi::parse::FuriParseErrorKind;
+    use test_case::test_case;
+
+    #[test_case("[音楽|おん|がく]")]
+    #[test_case("[音楽|おんがく]")]
+    #[test_case("おんがく")]
+    #[test_case("[A|]"; "empty reading")]
+    fn test_parse(s: &str) {
+        let seg = Segment::parse(s).unwrap();
+        assert_eq!(seg, SegmentRef::from_str_unchecked(s));
+        assert_eq!(Segment::from_str(s), Ok(seg));
+    }
+
+    #[test_case("[音楽|お|ん|がく]", FuriParseErrorKind::ReadingMismatch; "reading mismatch")]
+    #[test_case("[音楽]", FuriParseErrorKind::MissingReading; "missing reading")]
+    fn test_parse_fail(s: &str, kind: FuriParseErrorKind) {
+        let err = Segment::parse(s).unwrap_err();
+        assert_eq!(err.kind(), kind);
+        assert_eq!(err.pos(), 0);
+        assert_eq!(Segment::from_str(s), Err(()));
+    }
+
+    #[test_case("[音楽|おん|がく]", true; "detailed")]
+    #[test_case("[音楽|おんがく]", true; "merged")]
+    #[test_case("[音楽|おん]", true; "merged short")]
+    #[test_case("おんがく", true; "kana")]
+    #[test_case("[A|]", true; "empty reading")]
+    #[test_case("[拝金主義|は]", false; "too short")]
+    #[test_case("[音楽|おん|]", false; "detailed empty reading")]
+    fn test_is_plausible(s: &str, exp: bool) {
+        assert_eq!(Segment::parse(s).unwrap().is_plausible(), exp);
+    }
+}
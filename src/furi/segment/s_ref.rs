@@ -1,8 +1,19 @@
-use super::{kanji::KanjiRef, traits::AsSegment, Segment};
+use super::{
+    kanji::{as_kanji::AsKanjiSegment, KanjiRef},
+    traits::AsSegment,
+    Segment,
+};
+use crate::{furi::parse::FuriParseErrorKind, reading::ReadingRef};
+use itertools::Either;
+use std::{
+    fmt::{self, Display, Formatter},
+    hash::{Hash, Hasher},
+    iter,
+};
 use tinyvec::TinyVec;
 
 /// A single segment of a Furigana formatted string. Either holds a Kana or Kanji part.
-#[derive(Clone, PartialEq, Debug, Eq, Hash)]
+#[derive(Clone, PartialEq, Debug, Eq)]
 #[cfg_attr(feature = "with_serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SegmentRef<'a> {
     Kana(&'a str),
@@ -31,7 +42,7 @@ impl<'a> SegmentRef<'a> {
     /// Parses a ReadingPart from string
     pub fn from_str_checked(str: &'a str) -> Result<SegmentRef, ()> {
         if str.starts_with('[') && str.ends_with(']') {
-            Self::parse_kanji_str(str, true).ok_or(())
+            Self::parse_kanji_str(str, true).map_err(|_| ())
         } else {
             Ok(SegmentRef::Kana(str))
         }
@@ -51,27 +62,34 @@ impl<'a> SegmentRef<'a> {
     /// Is `check` == `true` the literals and kanji readings have to match up (except if there is only
     /// one reading) and there has to be at least a single reading. If `check` == `false` no
     /// checks a made and a parsed Segment will always be returned.
-    fn parse_kanji_str(s: &'a str, checked: bool) -> Option<SegmentRef> {
+    /// Blocks without kanji literals but a single reading, eg. `[|おん]`, are parsed as kana segment
+    /// holding the reading.
+    fn parse_kanji_str(s: &'a str, checked: bool) -> Result<SegmentRef, FuriParseErrorKind> {
         // Strip [ and ] and split at the |
         let mut split = s[1..s.len() - 1].split('|');
 
-        // First item is the kanji reading
-        let kanji = split.next()?;
+        // First item is the kanji reading. Split always returns at least one item.
+        let kanji = split.next().unwrap_or_default();
 
         let readings = split.collect::<TinyVec<[&str; 1]>>();
         if readings.is_empty() && checked {
-            return None;
+            return Err(FuriParseErrorKind::MissingReading);
         }
 
         if readings.len() == 1 {
+            if kanji.is_empty() {
+                // Reading-only block
+                return Ok(SegmentRef::Kana(readings[0]));
+            }
+
             // Fallback where all kanji get the first reading assigned
-            return Some(SegmentRef::new_kanji_raw(kanji, readings));
+            return Ok(SegmentRef::new_kanji_raw(kanji, readings));
         } else if checked && kanji.chars().count() != readings.len() {
             // Malformed kanji string
-            return None;
+            return Err(FuriParseErrorKind::ReadingMismatch);
         }
 
-        Some(SegmentRef::Kanji(KanjiRef::new_raw(kanji, readings)))
+        Ok(SegmentRef::Kanji(KanjiRef::new_raw(kanji, readings)))
     }
 
     /// Converts the SegmentRef to a Segment.
@@ -83,11 +101,78 @@ impl<'a> SegmentRef<'a> {
         }
     }
 
+    /// Returns the segment as [`ReadingRef`] borrowing from the segment without allocating. Kanji
+    /// segments with more than one reading can't be borrowed as a single kana reading, in which
+    /// case `None` is returned. Use [`AsSegment::to_reading`] for those instead.
+    pub fn to_reading_ref(&self) -> Option<ReadingRef<'a>> {
+        match self {
+            SegmentRef::Kana(kana) => Some(ReadingRef::new(kana)),
+            SegmentRef::Kanji(kanji) => match kanji.readings() {
+                [reading] => Some(ReadingRef::new_with_kanji(reading, kanji.literals())),
+                _ => None,
+            },
+        }
+    }
+
+    /// Returns an iterator over the readings of the segment like [`AsSegment::reading_iter`] but
+    /// borrows from the segment instead of allocating each reading. As each kanji literal of a
+    /// detailed block is a `char`, the literals are borrowed by splitting the literal string at
+    /// the char boundaries. Malformed kanji blocks whose readings can't be assigned to the
+    /// literals don't yield any reading.
+    pub fn reading_refs(&self) -> impl Iterator<Item = ReadingRef<'a>> + '_ {
+        let kanji = match self {
+            SegmentRef::Kana(kana) => return Either::Left(iter::once(ReadingRef::new(kana))),
+            SegmentRef::Kanji(kanji) => kanji,
+        };
+
+        let detailed = kanji.is_detailed();
+        let readings = kanji.readings();
+        let count = if detailed || readings.len() == 1 {
+            readings.len()
+        } else {
+            0
+        };
+
+        let mut lits = *kanji.literals();
+        Either::Right(readings.iter().take(count).map(move |reading| {
+            let lit = if detailed {
+                let len = lits.chars().next().map_or(0, char::len_utf8);
+                let (lit, rest) = lits.split_at(len);
+                lits = rest;
+                lit
+            } else {
+                lits
+            };
+            ReadingRef::new_with_kanji(reading, lit)
+        }))
+    }
+
+    /// Returns a new segment with `f` applied to each kana reading without allocating the readings.
+    /// For kana segments `f` is applied to the kana itself. This can be used for transformations
+    /// that borrow from the readings, like trimming.
+    pub fn map_readings<'b, F>(&self, f: F) -> SegmentRef<'b>
+    where
+        'a: 'b,
+        F: Fn(&'a str) -> &'b str,
+    {
+        match self {
+            SegmentRef::Kana(kana) => SegmentRef::Kana(f(kana)),
+            SegmentRef::Kanji(kanji) => {
+                let readings = kanji.readings().iter().map(|i| f(i)).collect();
+                SegmentRef::new_kanji_raw(kanji.literals(), readings)
+            }
+        }
+    }
+
     /// Parses a ReadingPart from string with `kanji` as parameter to give a hint whether its a
     /// kanji or kana segment. This avoids additional checks.
-    pub(crate) fn parse_str(str: &'a str, kanji: bool, checked: bool) -> Result<SegmentRef, ()> {
+    pub(crate) fn parse_str(
+        str: &'a str,
+        kanji: bool,
+        checked: bool,
+    ) -> Result<SegmentRef, FuriParseErrorKind> {
         if kanji {
-            Self::parse_kanji_str(str, checked).ok_or(())
+            Self::parse_kanji_str(str, checked)
         } else {
             Ok(SegmentRef::Kana(str))
         }
@@ -127,6 +212,20 @@ impl<'a> AsSegment for SegmentRef<'a> {
     }
 }
 
+impl<'a> Display for SegmentRef<'a> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        super::fmt_segment(self, f)
+    }
+}
+
+impl<'a> Hash for SegmentRef<'a> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        super::hash_segment(self, state);
+    }
+}
+
 impl<'a> PartialEq<Segment> for SegmentRef<'a> {
     #[inline]
     fn eq(&self, other: &Segment) -> bool {
@@ -140,3 +239,147 @@ impl<'a> PartialEq<Segment> for &'a SegmentRef<'a> {
         other.eq(*self)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_to_reading_ref() {
+        let src = String::from("おんがく");
+        let seg = SegmentRef::new_kana(&src);
+        let reading = seg.to_reading_ref().unwrap();
+        assert_eq!(reading, ReadingRef::new("おんがく"));
+        assert_eq!(reading.kana().as_ptr(), src.as_ptr());
+
+        let seg = SegmentRef::from_str_unchecked("[音楽|おんがく]");
+        assert_eq!(
+            seg.to_reading_ref(),
+            Some(ReadingRef::new_with_kanji("おんがく", "音楽"))
+        );
+
+        let seg = SegmentRef::from_str_unchecked("[音楽|おん|がく]");
+        assert_eq!(seg.to_reading_ref(), None);
+    }
+
+    #[test]
+    fn test_matches_kana() {
+        let seg = SegmentRef::from_str_unchecked("[音楽|おん|がく]");
+        assert!(seg.matches_kana("おんがく"));
+        assert!(seg.to_owned().matches_kana("おんがく"));
+        assert!(!seg.matches_kana("おんが"));
+        assert!(!seg.matches_kana("おんがくが"));
+        assert!(!seg.matches_kana("音楽"));
+
+        assert!(SegmentRef::from_str_unchecked("[音楽|おんがく]").matches_kana("おんがく"));
+        assert!(SegmentRef::new_kana("おんがく").matches_kana("おんがく"));
+        assert!(!SegmentRef::new_kana("おんがく").matches_kana("オンガク"));
+    }
+
+    #[test]
+    fn test_reading_refs() {
+        for s in [
+            "[音楽|おん|がく]",
+            "[音楽|おんがく]",
+            "[好|す]",
+            "[A|]",
+            "おんがく",
+        ] {
+            let seg = SegmentRef::from_str_unchecked(s);
+            let refs: Vec<_> = seg.reading_refs().collect();
+            let owned: Vec<_> = seg.reading_iter().collect();
+            assert_eq!(refs.len(), owned.len());
+            for (r, o) in refs.iter().zip(owned) {
+                assert_eq!(o, *r);
+            }
+        }
+
+        let seg = SegmentRef::from_str_unchecked("[音楽|おん|がく]");
+        assert_eq!(
+            seg.reading_refs().collect::<Vec<_>>(),
+            vec![
+                ReadingRef::new_with_kanji("おん", "音"),
+                ReadingRef::new_with_kanji("がく", "楽"),
+            ]
+        );
+
+        let seg = SegmentRef::from_str_unchecked("[音楽|お|ん|がく]");
+        assert_eq!(seg.reading_refs().count(), 0);
+    }
+
+    #[test]
+    fn test_map_readings() {
+        let seg = SegmentRef::new_kanji("音楽", &[" おん", "がく "]);
+        assert_eq!(
+            seg.map_readings(str::trim),
+            SegmentRef::new_kanji("音楽", &["おん", "がく"])
+        );
+
+        let seg = SegmentRef::new_kana(" おんがく ");
+        assert_eq!(
+            seg.map_readings(str::trim),
+            SegmentRef::new_kana("おんがく")
+        );
+
+        let seg = SegmentRef::new_kanji("音楽", &["おんがく"]);
+        let mapped = seg.map_readings(|i| &i[..6]);
+        assert_eq!(mapped, SegmentRef::new_kanji("音楽", &["おん"]));
+    }
+
+    #[test]
+    fn test_display() {
+        let seg = SegmentRef::from_str_unchecked("[音楽|おん|がく]");
+        assert_eq!(format!("{seg}"), "[音楽|おん|がく]");
+        assert_eq!(format!("{seg:#}"), "音楽(おんがく)");
+        assert_eq!(format!("{:#}", seg.to_owned()), "音楽(おんがく)");
+        assert_eq!(seg.to_owned().to_string(), "[音楽|おん|がく]");
+
+        let seg = SegmentRef::new_kana("が");
+        assert_eq!(format!("{seg}"), "が");
+        assert_eq!(format!("{seg:#}"), "が");
+    }
+
+    #[test]
+    fn test_reading_width() {
+        assert_eq!(SegmentRef::new_kana("おんがく").reading_width(), 4);
+        assert_eq!(SegmentRef::new_kana("").reading_width(), 0);
+
+        let seg = SegmentRef::from_str_unchecked("[音楽|おん|がく]");
+        assert_eq!(seg.reading_width(), 4);
+        assert_eq!(seg.to_owned().reading_width(), 4);
+
+        let seg = SegmentRef::from_str_unchecked("[拝金主義|はい|きん|しゅ|ぎ]");
+        assert_eq!(seg.reading_width(), 7);
+        assert_eq!(SegmentRef::from_str_unchecked("[A|]").reading_width(), 0);
+    }
+
+    #[test]
+    fn test_hash_consistency() {
+        use std::collections::{hash_map::DefaultHasher, HashSet};
+
+        fn hash<T: Hash>(t: &T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            t.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        for s in ["[音楽|おん|がく]", "[音楽|おんがく]", "おんがく", "[A|]"] {
+            let seg_ref = SegmentRef::from_str_unchecked(s);
+            let seg = seg_ref.to_owned();
+            assert_eq!(seg, seg_ref);
+            assert_eq!(hash(&seg), hash(&seg_ref));
+        }
+
+        let a = SegmentRef::from_str_unchecked("[音楽|おん|がく]");
+        let b = SegmentRef::from_str_unchecked("[音楽|おんがく]");
+        assert_ne!(hash(&a), hash(&b));
+        assert_ne!(
+            hash(&SegmentRef::Kana("a")),
+            hash(&SegmentRef::new_kanji("a", &[]))
+        );
+
+        // Hash based lookups of owned segments can be done with borrowed segments.
+        let set: HashSet<u64> = [hash(&a.to_owned())].into_iter().collect();
+        assert!(set.contains(&hash(&a)));
+    }
+}
@@ -81,10 +81,32 @@ pub trait AsSegment {
         enc.write_kanji(unsafe { self.as_kanji().unwrap_unchecked() });
     }
 
+    /// Returns the amount of bytes the encoded segment takes. This can be used to reserve enough
+    /// capacity before encoding.
+    fn encoded_len_hint(&self) -> usize {
+        if let Some(kana) = self.as_kana() {
+            return kana.as_ref().len();
+        }
+
+        // Safe as there can only be kanji or kana and in case of kana this function had early
+        // returned.
+        let kanji = unsafe { self.as_kanji().unwrap_unchecked() };
+        let readings = kanji.readings();
+        let readings_len: usize = readings.iter().map(|i| i.as_ref().len()).sum();
+        let separators = if kanji.is_detailed() {
+            readings.len().max(1)
+        } else {
+            1
+        };
+
+        // Brackets + literals + separators + readings
+        2 + kanji.literals().as_ref().len() + separators + readings_len
+    }
+
     /// Encodes the segment into a newly allocated String. This shouldn't be used in loops or
     /// situations where `encode_into` would work too as this does less allocations.
     fn encode(&self) -> String {
-        let mut buf = String::with_capacity(8);
+        let mut buf = String::with_capacity(self.encoded_len_hint());
         self.encode_into(&mut buf);
         buf
     }
@@ -110,6 +132,17 @@ pub trait AsSegment {
         FlattenIter::new(self)
     }
 
+    /// Returns an iterator over flattened readings which, unlike `reading_flattened`, keeps kanji
+    /// blocks with multiple literals as a single segment. Useful for word instead of character
+    /// boundaries.
+    #[inline]
+    fn reading_flattened_grouped(&self) -> FlattenIter<'_, Self::StrType, Self::KanjiType>
+    where
+        Self: Sized,
+    {
+        FlattenIter::new_grouped(self)
+    }
+
     /// Returns `true` if the segment holds equal reading data as `reading`.
     fn eq_reading<R>(&self, reading: R) -> bool
     where
@@ -134,6 +167,46 @@ pub trait AsSegment {
         let kanji = unsafe { self.as_kanji().unwrap_unchecked() };
         kanji.literals().as_ref() == reading_kanji && self.get_kana_reading() == reading.kana()
     }
+
+    /// Returns `true` if the kana reading of the segment equals `kana`. This is the case for kana
+    /// segments holding `kana` and kanji segments whose readings form `kana`.
+    fn matches_kana(&self, kana: &str) -> bool {
+        if let Some(s) = self.as_kana() {
+            return s.as_ref() == kana;
+        }
+
+        // Safety:
+        // A segment is either a kanji or kana. This is unreachable if its not kanji.
+        let kanji = unsafe { self.as_kanji().unwrap_unchecked() };
+
+        // Compare each reading without allocating the full reading.
+        let mut rest = kana;
+        for reading in kanji.readings() {
+            match rest.strip_prefix(reading.as_ref()) {
+                Some(r) => rest = r,
+                None => return false,
+            }
+        }
+        rest.is_empty()
+    }
+
+    /// Returns the amount of characters of the kana reading of the segment. For kanji segments
+    /// this is the length of all readings merged. Can be used to determine the width of the ruby
+    /// text when rendering.
+    fn reading_width(&self) -> usize {
+        if let Some(kana) = self.as_kana() {
+            return kana.as_ref().chars().count();
+        }
+
+        // Safety:
+        // A segment is either a kanji or kana. This is unreachable if its not kanji.
+        let kanji = unsafe { self.as_kanji().unwrap_unchecked() };
+        kanji
+            .readings()
+            .iter()
+            .map(|i| i.as_ref().chars().count())
+            .sum()
+    }
 }
 
 impl<T> AsSegment for &T
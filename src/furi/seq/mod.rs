@@ -6,12 +6,15 @@ use self::{
     reading::SReading,
 };
 use super::{
-    parse::FuriParser,
-    segment::{AsSegment, Segment, SegmentRef},
+    parse::{FuriParseError, FuriParser},
+    segment::{
+        kanji::{as_kanji::AsKanjiSegment, Kanji},
+        AsSegment, Segment, SegmentRef,
+    },
     Furigana,
 };
 use crate::reading::Reading;
-use std::{slice::Iter, str::FromStr};
+use std::{collections::HashSet, ops::Range, slice::Iter, str::FromStr};
 
 /// Sequence of parsed furigana segments. This type can be helpful if you access the inner parts a
 /// lot. Otherwise you should use [`crate::furigana::Furigana`] instead as its memory efficient and
@@ -90,6 +93,13 @@ where
         self.parts.get(pos)
     }
 
+    /// Returns a new sequence borrowing the parts within the given range. Parts out of bounds
+    /// are ignored.
+    #[inline]
+    pub fn slice(&self, r: Range<usize>) -> FuriSequence<&T> {
+        self.parts.iter().skip(r.start).take(r.len()).collect()
+    }
+
     /// Push a part to the end of the sequence
     #[inline]
     pub fn push_part(&mut self, part: T) {
@@ -102,6 +112,18 @@ where
         self.parts.iter()
     }
 
+    /// Returns an iterator over all kanji parts of the sequence.
+    #[inline]
+    pub fn kanji_parts(&self) -> impl Iterator<Item = &T> {
+        self.parts.iter().filter(|i| i.is_kanji())
+    }
+
+    /// Returns an iterator over all kana parts of the sequence.
+    #[inline]
+    pub fn kana_parts(&self) -> impl Iterator<Item = &T> {
+        self.parts.iter().filter(|i| i.is_kana())
+    }
+
     /// Returns an iterator over all reading parts with kanji readings split into separate
     /// ReadingParts.
     #[inline]
@@ -109,6 +131,15 @@ where
         self.parts.iter().flat_map(|i| i.reading_flattened())
     }
 
+    /// Returns an iterator over all reading parts like `flattened_iter` but keeps kanji parts
+    /// with multiple literals as a single part.
+    #[inline]
+    pub fn flattened_grouped_iter(&self) -> impl Iterator<Item = Segment> + '_ {
+        self.parts
+            .iter()
+            .flat_map(|i| i.reading_flattened_grouped())
+    }
+
     /// Converts the sequence into a Vec of its parts
     #[inline]
     pub fn into_parts(self) -> Vec<T> {
@@ -132,12 +163,22 @@ where
     pub fn to_reading(&self) -> Reading {
         Reading::from_iter(self.parts.iter())
     }
+
+    /// Returns the readings of all segments without duplicates in the order they first occur.
+    pub fn unique_readings(&self) -> Vec<Reading> {
+        let mut seen = HashSet::with_capacity(self.parts.len());
+        self.parts
+            .iter()
+            .map(|i| i.to_reading())
+            .filter(|i| seen.insert(i.clone()))
+            .collect()
+    }
 }
 
 impl<'a> FuriSequence<SegmentRef<'a>> {
     /// Parse a referencd FuriSequence from a `str`
     #[inline]
-    pub fn parse_ref(s: &'a str) -> Result<FuriSequence<SegmentRef<'a>>, ()> {
+    pub fn parse_ref(s: &'a str) -> Result<FuriSequence<SegmentRef<'a>>, FuriParseError> {
         FuriParser::new(s).collect()
     }
 
@@ -147,15 +188,69 @@ impl<'a> FuriSequence<SegmentRef<'a>> {
     }
 }
 
-impl FromStr for FuriSequence<Segment> {
-    type Err = ();
-
+impl FuriSequence<Segment> {
+    /// Parse an owned FuriSequence from a `str`.
     #[inline]
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    pub fn parse_owned(s: &str) -> Result<FuriSequence<Segment>, FuriParseError> {
         FuriParser::new(s)
             .map(|i| i.map(|i| i.to_owned()))
             .collect::<Result<_, _>>()
     }
+
+    /// Rewrites every kanji reading and kana part of the sequence using `f`.
+    pub fn map_readings<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&str) -> String,
+    {
+        for part in self.parts.iter_mut() {
+            match part {
+                Segment::Kana(kana) => *kana = f(kana),
+                Segment::Kanji(kanji) => {
+                    for reading in kanji.readings_mut() {
+                        *reading = f(reading);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Merges all adjacent kanji segments with detailed readings into a single detailed kanji
+    /// segment, eg. `[音|おん][楽|がく]` into `[音楽|おん|がく]`. This is the equivalent of
+    /// [`CodeFormatter::merge_kanji_parts`](super::cformat::CodeFormatter::merge_kanji_parts).
+    pub fn merge_kanji(&mut self) {
+        fn mergeable(kanji: &Kanji) -> bool {
+            kanji.is_detailed() && !kanji.readings().iter().any(|r| r.is_empty())
+        }
+
+        let mut merged: Vec<Segment> = Vec::with_capacity(self.parts.len());
+        for part in self.parts.drain(..) {
+            if let (Some(Segment::Kanji(prev)), Segment::Kanji(kanji)) = (merged.last_mut(), &part)
+            {
+                if mergeable(prev) && mergeable(kanji) {
+                    let lits = format!("{}{}", prev.literals(), kanji.literals());
+                    let readings: Vec<_> = prev
+                        .readings()
+                        .iter()
+                        .chain(kanji.readings())
+                        .cloned()
+                        .collect();
+                    *prev = Kanji::new(lits, &readings);
+                    continue;
+                }
+            }
+            merged.push(part);
+        }
+        self.parts = merged;
+    }
+}
+
+impl FromStr for FuriSequence<Segment> {
+    type Err = FuriParseError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_owned(s)
+    }
 }
 
 impl<T: AsSegment> ToString for FuriSequence<T> {
@@ -223,6 +318,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::JapaneseExt;
     use test_case::test_case;
 
     #[test_case("[音楽|おん|がく]が[好|す]き", "おんがくがすき"; "seq_to_kana1")]
@@ -260,6 +356,132 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_unique_readings() {
+        let seq = FuriSequence::parse_ref(
+            "[私|わたし]は[猫|ねこ]が[好|す]きで、[犬|いぬ]は[好|す]きじゃない",
+        )
+        .unwrap();
+        let readings: Vec<String> = seq
+            .unique_readings()
+            .into_iter()
+            .map(|i| i.to_string())
+            .collect();
+        assert_eq!(
+            readings,
+            vec![
+                "私（わたし）",
+                "は",
+                "猫（ねこ）",
+                "が",
+                "好（す）",
+                "きで、",
+                "犬（いぬ）",
+                "きじゃない"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_slice() {
+        let seq = FuriSequence::parse_ref("[音楽|おん|がく]が[好|す]きです").unwrap();
+
+        let slice = seq.slice(1..3);
+        assert_eq!(slice.len(), 2);
+        assert_eq!(slice.as_kana(), "がす");
+        assert_eq!(slice.as_kanji(), "が好");
+        assert_eq!(slice.encode(), Furigana("が[好|す]"));
+
+        assert_eq!(seq.slice(3..10).as_kana(), "きです");
+        assert!(seq.slice(5..10).is_empty());
+    }
+
+    #[test]
+    fn test_kanji_kana_parts() {
+        let seq = FuriSequence::parse_ref("[音楽|おん|がく]が[好|す]きです").unwrap();
+
+        let kanji: Vec<_> = seq.kanji_parts().collect();
+        assert_eq!(
+            kanji,
+            vec![
+                &SegmentRef::new_kanji("音楽", &["おん", "がく"]),
+                &SegmentRef::new_kanji("好", &["す"]),
+            ]
+        );
+
+        let kana: Vec<_> = seq.kana_parts().collect();
+        assert_eq!(
+            kana,
+            vec![&SegmentRef::new_kana("が"), &SegmentRef::new_kana("きです")]
+        );
+    }
+
+    #[test]
+    fn test_flattened_grouped() {
+        let seq = FuriSequence::parse_owned("[音楽|おん|がく]が[大好|だい|す]き").unwrap();
+        let grouped: Vec<_> = seq.flattened_grouped_iter().collect();
+        assert_eq!(grouped, seq.clone().into_parts());
+
+        let flattened: Vec<_> = seq.flattened_iter().collect();
+        assert_eq!(
+            flattened,
+            vec![
+                SegmentRef::new_kanji("音", &["おん"]),
+                SegmentRef::new_kanji("楽", &["がく"]),
+                SegmentRef::new_kana("が"),
+                SegmentRef::new_kanji("大", &["だい"]),
+                SegmentRef::new_kanji("好", &["す"]),
+                SegmentRef::new_kana("き"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_map_readings() {
+        let mut seq = FuriSequence::parse_owned("[音楽|おん|がく]が[好|す]き").unwrap();
+        seq.map_readings(|r| r.to_katakana());
+        assert_eq!(seq.encode(), Furigana("[音楽|オン|ガク]ガ[好|ス]キ"));
+
+        seq.map_readings(|r| r.replace('キ', "き"));
+        assert_eq!(seq.encode(), Furigana("[音楽|オン|ガク]ガ[好|ス]き"));
+    }
+
+    #[test_case("[大|だい][丈|じょう][夫|ぶ]", "[大丈夫|だい|じょう|ぶ]"; "all kanji")]
+    #[test_case("それは[大|だい][丈|じょう]です", "それは[大丈|だい|じょう]です"; "kana before and after")]
+    #[test_case("それは[大|だい][丈夫|じょうぶ]だよ", "それは[大|だい][丈夫|じょうぶ]だよ"; "non detailed")]
+    #[test_case("それは[音|おん][楽|がく][大学|だいがく]です", "それは[音楽|おん|がく][大学|だいがく]です"; "non detailed2")]
+    #[test_case("それは[音|おん][楽|がく][大学|だい|がく]です", "それは[音楽大学|おん|がく|だい|がく]です"; "non detailed3")]
+    #[test_case(
+        "[高校生|こう|こう|せい]の[時|とき]は[毎朝|まい|あさ][6|][時|じ]に[起|お]きていた。",
+        "[高校生|こう|こう|せい]の[時|とき]は[毎朝|まい|あさ][6|][時|じ]に[起|お]きていた。"; "empty kanji"
+    )]
+    fn test_merge_kanji(src: &str, dst: &str) {
+        let mut seq = FuriSequence::parse_owned(src).unwrap();
+        seq.merge_kanji();
+        assert_eq!(seq.encode(), Furigana(dst));
+        assert_eq!(
+            seq.encode(),
+            Furigana(src).code_formatter().merge_kanji_parts().finish()
+        );
+    }
+
+    #[test_case("[音楽|おん|がく]が[好|す]き"; "valid")]
+    #[test_case("[拝金主義|はい|きん|しゅ|ぎ]は[問題|もん|だい]"; "valid2")]
+    fn test_parse_owned(furi: &str) {
+        let owned = FuriSequence::parse_owned(furi).unwrap();
+        assert_eq!(owned, FuriSequence::parse_ref(furi).unwrap().to_owned());
+        assert_eq!(owned, FuriSequence::from_str(furi).unwrap());
+    }
+
+    #[test_case("[音楽|お|ん|がく]が[好|す]き", 0; "first")]
+    #[test_case("[音楽|おん|がく]が[好|す|き]", 25; "last")]
+    fn test_parse_err_pos(furi: &str, pos: usize) {
+        let err = FuriSequence::parse_owned(furi).unwrap_err();
+        assert_eq!(err.pos(), pos);
+        assert_eq!(err, FuriSequence::parse_ref(furi).unwrap_err());
+        assert_eq!(err, FuriSequence::from_str(furi).unwrap_err());
+    }
+
     #[cfg(feature = "serde")]
     #[test_case("[音楽|おんがく]が[好|す]き"; "serde1")]
     #[test_case("[拝金主義|はい|きん|しゅ|ぎ]は[問題|もん|だい][拝金主義|はい|きん|しゅ|ぎ]は[問題|もん|だい][拝金主義|はい|きん|しゅ|ぎ]は[問題|もん|だい]"; "serde2")]
@@ -1,4 +1,5 @@
-use std::fmt::Display;
+use crate::JapaneseExt;
+use std::{fmt::Display, iter};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Vowel {
@@ -22,6 +23,22 @@ impl Vowel {
     }
 }
 
+/// Romaji written for a `っ` which isn't followed by a consonant.
+const DANGLING_SOKUON: char = '\'';
+
+/// How long vowels (eg. `おう`) are written in romaji.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LongVowel {
+    /// Long vowels are written with a macron [とうきょう -> tōkyō]
+    Macron,
+
+    /// Long vowels are written as doubled vowel [とうきょう -> tookyoo]
+    Doubled,
+
+    /// Long vowels are written as they are spelled in kana [とうきょう -> toukyou]
+    Literal,
+}
+
 impl Into<Vowel> for char {
     #[inline]
     fn into(self) -> Vowel {
@@ -36,14 +53,149 @@ impl Into<Vowel> for char {
     }
 }
 
+/// Converts a kana string to romaji writing long vowels as they are spelled in kana. This is
+/// equal to [`to_romaji_with`] using [`LongVowel::Literal`]. Returns `None` if `s` contains
+/// characters that can't be converted.
+#[inline]
 pub fn to_romaji(s: &str) -> Option<String> {
+    to_romaji_with(s, LongVowel::Literal)
+}
+
+/// Converts a kana string to romaji. Small kana are combined with the kana before them
+/// (`きょ -> kyo`), `っ` doubles the following consonant and long vowels are written as specified
+/// by `long_vowel`. A `っ` which isn't followed by a consonant is written as `'`. Returns `None` if
+/// `s` contains characters that can't be converted.
+pub fn to_romaji_with(s: &str, long_vowel: LongVowel) -> Option<String> {
     let mut out = String::with_capacity(s.len());
-    for c in s.chars() {
-        out.push_str(&Syllable::from_char(c).get_splitted()?.to_romaji_char());
+    let mut prev_vowel: Option<Vowel> = None;
+    let mut sokuon = false;
+
+    for mora in moras(s) {
+        let mut chars = mora.chars();
+        let first = chars.next()?;
+
+        if first == 'っ' || first == 'ッ' {
+            if sokuon {
+                out.push(DANGLING_SOKUON);
+            }
+            sokuon = true;
+            prev_vowel = None;
+            continue;
+        }
+
+        if let Some(vowel) = prev_vowel.take() {
+            if mora.chars().count() == 1 && lengthens_vowel(vowel, first) {
+                match long_vowel {
+                    LongVowel::Macron => {
+                        out.pop();
+                        out.push(vowel_macron(vowel));
+                    }
+                    LongVowel::Doubled => out.push(vowel.to_romaji()),
+                    LongVowel::Literal if first == 'ー' => out.push(vowel.to_romaji()),
+                    LongVowel::Literal => {
+                        out.push_str(&Syllable::from_char(first).to_romaji_char()?)
+                    }
+                }
+                continue;
+            }
+        }
+
+        let mut split = Syllable::from_char(first).get_splitted()?;
+        let mut youon = false;
+        if let Some(small) = chars.next() {
+            let small = small.normalize_small_kana().chars().next()?;
+            let small_split = Syllable::from_char(small).get_splitted()?;
+            youon = small_split.consonant == Some(Consonant::Y);
+            split.vowel = small_split.vowel;
+        }
+
+        let (consonant, vowel) = split.in_romaji();
+        if sokuon {
+            out.push(consonant.unwrap_or(DANGLING_SOKUON));
+            sokuon = false;
+        }
+        out.extend(consonant);
+        if youon {
+            out.push('y');
+        }
+        out.extend(vowel);
+
+        prev_vowel = split.vowel;
     }
+
+    if sokuon {
+        out.push(DANGLING_SOKUON);
+    }
+
     Some(out)
 }
 
+/// Returns the vowel of each mora of a kana string, so `かきく` yields `[A, I, U]`. `ん`, `っ` and
+/// characters that aren't kana have no vowel and are skipped. `ー` repeats the previous vowel.
+pub fn vowel_sequence(s: &str) -> Vec<Vowel> {
+    let mut out: Vec<Vowel> = Vec::with_capacity(s.len() / 3);
+
+    for mora in moras(s) {
+        // The last character of a mora determines its vowel (eg. `きょ`).
+        let Some(c) = mora.chars().last() else {
+            continue;
+        };
+
+        if c == 'ー' {
+            if let Some(prev) = out.last().copied() {
+                out.push(prev);
+            }
+            continue;
+        }
+
+        let c = c.normalize_small_kana().chars().next().unwrap_or(c);
+        if let Some(vowel) = Syllable::from_char(c).get_splitted().and_then(|i| i.vowel) {
+            out.push(vowel);
+        }
+    }
+
+    out
+}
+
+/// Returns `true` if the kana `c` lengthens the vowel `prev`.
+#[inline]
+fn lengthens_vowel(prev: Vowel, c: char) -> bool {
+    match c {
+        'ー' => true,
+        'う' | 'ウ' => prev == Vowel::O || prev == Vowel::U,
+        'お' | 'オ' => prev == Vowel::O,
+        _ => false,
+    }
+}
+
+/// Returns the romaji vowel with a macron.
+#[inline]
+fn vowel_macron(vowel: Vowel) -> char {
+    match vowel {
+        Vowel::A => 'ā',
+        Vowel::E => 'ē',
+        Vowel::I => 'ī',
+        Vowel::O => 'ō',
+        Vowel::U => 'ū',
+    }
+}
+
+/// Returns an iterator over all mora of a kana string. Small kana (eg. `ゃ`) are grouped together
+/// with the kana before them, so `きょう` yields `きょ` and `う`. `っ` and `ー` are returned as
+/// their own mora.
+pub fn moras(s: &str) -> impl Iterator<Item = &str> {
+    let mut chars = s.char_indices().peekable();
+
+    iter::from_fn(move || {
+        let (start, _) = chars.next()?;
+
+        while chars.next_if(|(_, c)| c.is_small_kana()).is_some() {}
+
+        let end = chars.peek().map(|i| i.0).unwrap_or(s.len());
+        Some(&s[start..end])
+    })
+}
+
 /// One single syllable within the a kana alphabet
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct Syllable(char);
@@ -174,14 +326,20 @@ impl Syllable {
     pub fn get_splitted(&self) -> Option<SyllableSplit> {
         let c = self.0;
 
-        if c == 'ん' {
+        if c == 'ん' || c == 'ン' {
             return Some(SyllableSplit {
                 consonant: Some(Consonant::NSpecial),
                 vowel: None,
             });
         }
 
-        for (row, letters) in HIRAGANA_SYLLABLES {
+        let syllables = if self.is_katakana_char() {
+            KATAKANA_SYLLABLES
+        } else {
+            HIRAGANA_SYLLABLES
+        };
+
+        for (row, letters) in syllables {
             for (character, vowel) in *letters {
                 if *character == c {
                     if *row == Consonant::Vowels {
@@ -204,6 +362,12 @@ impl Syllable {
     /// Returns the character with dakuten
     #[inline]
     pub fn to_dakuten(&self) -> Self {
+        if self.is_katakana_char() {
+            // Katakana are located 0x60 after their hiragana equivalents.
+            let hira = Self::from(char::from_u32(self.0 as u32 - 0x60).unwrap());
+            return Self::from(char::from_u32(hira.to_dakuten().0 as u32 + 0x60).unwrap());
+        }
+
         match self.get_char() {
             'た' => Self::from('だ'),
             'て' => Self::from('で'),
@@ -234,10 +398,16 @@ impl Syllable {
         self.0
     }
 
-    /// Returns true if the syllable is a valid (hiragana) character
+    /// Returns true if the syllable is a valid (hiragana or katakana) character
     pub fn is_valid(&self) -> bool {
         self.get_splitted().is_some()
     }
+
+    /// Returns `true` if the syllable is a katakana character
+    #[inline]
+    pub fn is_katakana_char(&self) -> bool {
+        ('\u{30A1}'..='\u{30F6}').contains(&self.0)
+    }
 }
 
 /// All (single) hiragana syllables
@@ -386,9 +556,230 @@ pub const HIRAGANA_SYLLABLES: &[(Consonant, &[(char, Vowel)])] = &[
     (Consonant::W, &[('わ', Vowel::A), ('を', Vowel::O)]),
 ];
 
+/// All (single) katakana syllables
+pub const KATAKANA_SYLLABLES: &[(Consonant, &[(char, Vowel)])] = &[
+    (
+        Consonant::Vowels,
+        &[
+            ('ア', Vowel::A),
+            ('エ', Vowel::E),
+            ('イ', Vowel::I),
+            ('オ', Vowel::O),
+            ('ウ', Vowel::U),
+        ],
+    ),
+    (
+        Consonant::K,
+        &[
+            ('カ', Vowel::A),
+            ('ケ', Vowel::E),
+            ('キ', Vowel::I),
+            ('コ', Vowel::O),
+            ('ク', Vowel::U),
+        ],
+    ),
+    (
+        Consonant::G,
+        &[
+            ('ガ', Vowel::A),
+            ('ゲ', Vowel::E),
+            ('ギ', Vowel::I),
+            ('ゴ', Vowel::O),
+            ('グ', Vowel::U),
+        ],
+    ),
+    (
+        Consonant::S,
+        &[
+            ('サ', Vowel::A),
+            ('セ', Vowel::E),
+            ('シ', Vowel::I),
+            ('ソ', Vowel::O),
+            ('ス', Vowel::U),
+        ],
+    ),
+    (
+        Consonant::Z,
+        &[
+            ('ザ', Vowel::A),
+            ('ゼ', Vowel::E),
+            ('ジ', Vowel::I),
+            ('ゾ', Vowel::O),
+            ('ズ', Vowel::U),
+        ],
+    ),
+    (
+        Consonant::T,
+        &[
+            ('タ', Vowel::A),
+            ('テ', Vowel::E),
+            ('チ', Vowel::I),
+            ('ト', Vowel::O),
+            ('ツ', Vowel::U),
+        ],
+    ),
+    (
+        Consonant::D,
+        &[
+            ('ダ', Vowel::A),
+            ('デ', Vowel::E),
+            ('ヂ', Vowel::I),
+            ('ド', Vowel::O),
+            ('ヅ', Vowel::U),
+        ],
+    ),
+    (
+        Consonant::N,
+        &[
+            ('ナ', Vowel::A),
+            ('ネ', Vowel::E),
+            ('ニ', Vowel::I),
+            ('ノ', Vowel::O),
+            ('ヌ', Vowel::U),
+        ],
+    ),
+    (
+        Consonant::H,
+        &[
+            ('ハ', Vowel::A),
+            ('ヘ', Vowel::E),
+            ('ヒ', Vowel::I),
+            ('ホ', Vowel::O),
+            ('フ', Vowel::U),
+        ],
+    ),
+    (
+        Consonant::B,
+        &[
+            ('バ', Vowel::A),
+            ('ベ', Vowel::E),
+            ('ビ', Vowel::I),
+            ('ボ', Vowel::O),
+            ('ブ', Vowel::U),
+        ],
+    ),
+    (
+        Consonant::P,
+        &[
+            ('パ', Vowel::A),
+            ('ペ', Vowel::E),
+            ('ピ', Vowel::I),
+            ('ポ', Vowel::O),
+            ('プ', Vowel::U),
+        ],
+    ),
+    (
+        Consonant::M,
+        &[
+            ('マ', Vowel::A),
+            ('メ', Vowel::E),
+            ('ミ', Vowel::I),
+            ('モ', Vowel::O),
+            ('ム', Vowel::U),
+        ],
+    ),
+    (
+        Consonant::R,
+        &[
+            ('ラ', Vowel::A),
+            ('レ', Vowel::E),
+            ('リ', Vowel::I),
+            ('ロ', Vowel::O),
+            ('ル', Vowel::U),
+        ],
+    ),
+    (
+        Consonant::Y,
+        &[
+            ('ヤ', Vowel::A),
+            ('ャ', Vowel::A),
+            ('ヨ', Vowel::O),
+            ('ョ', Vowel::O),
+            ('ユ', Vowel::U),
+            ('ュ', Vowel::U),
+        ],
+    ),
+    (Consonant::W, &[('ワ', Vowel::A), ('ヲ', Vowel::O)]),
+];
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use test_case::test_case;
+
+    #[test_case("きょうは", &["きょ", "う", "は"]; "youon")]
+    #[test_case("がっこう", &["が", "っ", "こ", "う"]; "small tsu")]
+    #[test_case("ラーメン", &["ラ", "ー", "メ", "ン"]; "choonpu")]
+    #[test_case("ファイル", &["ファ", "イ", "ル"]; "katakana")]
+    #[test_case("ゃく", &["ゃ", "く"]; "leading small kana")]
+    #[test_case("", &[]; "empty")]
+    fn test_moras(s: &str, exp: &[&str]) {
+        assert_eq!(moras(s).collect::<Vec<_>>(), exp);
+    }
+
+    #[test_case("とうきょう", LongVowel::Macron, "tōkyō")]
+    #[test_case("とうきょう", LongVowel::Doubled, "tookyoo")]
+    #[test_case("とうきょう", LongVowel::Literal, "toukyou")]
+    #[test_case("がっこう", LongVowel::Macron, "gakkō")]
+    #[test_case("ラーメン", LongVowel::Macron, "rāmen")]
+    #[test_case("ラーメン", LongVowel::Literal, "raamen")]
+    #[test_case("おおきい", LongVowel::Doubled, "ookii")]
+    #[test_case("すうがく", LongVowel::Macron, "sūgaku")]
+    #[test_case("あっ", LongVowel::Literal, "a'"; "trailing sokuon")]
+    #[test_case("ッ", LongVowel::Macron, "'"; "only sokuon")]
+    #[test_case("あっあ", LongVowel::Literal, "a'a"; "sokuon before vowel")]
+    #[test_case("えっっと", LongVowel::Literal, "e'tto"; "double sokuon")]
+    fn test_to_romaji_with(s: &str, long_vowel: LongVowel, exp: &str) {
+        assert_eq!(to_romaji_with(s, long_vowel).as_deref(), Some(exp));
+    }
+
+    #[test_case("とうきょう", "toukyou")]
+    #[test_case("がっこう", "gakkou")]
+    #[test_case("ラーメン", "raamen")]
+    #[test_case("あっ", "a'"; "trailing sokuon")]
+    fn test_to_romaji(s: &str, exp: &str) {
+        assert_eq!(to_romaji(s).as_deref(), Some(exp));
+        assert_eq!(to_romaji(s), to_romaji_with(s, LongVowel::Literal));
+    }
+
+    #[test_case("かきく", &[Vowel::A, Vowel::I, Vowel::U])]
+    #[test_case("きょう", &[Vowel::O, Vowel::U]; "youon")]
+    #[test_case("がっこう", &[Vowel::A, Vowel::O, Vowel::U]; "small tsu")]
+    #[test_case("ほん", &[Vowel::O]; "n")]
+    #[test_case("ラーメン", &[Vowel::A, Vowel::A, Vowel::E]; "choonpu")]
+    #[test_case("", &[]; "empty")]
+    fn test_vowel_sequence(s: &str, exp: &[Vowel]) {
+        assert_eq!(vowel_sequence(s), exp);
+    }
+
+    #[test]
+    fn test_to_romaji_with_invalid() {
+        assert_eq!(to_romaji_with("音楽", LongVowel::Macron), None);
+    }
+
+    #[test]
+    fn test_katakana() {
+        let ka = Syllable::from_char('カ');
+        assert!(ka.is_katakana_char());
+        assert!(!Syllable::from_char('か').is_katakana_char());
+        assert_eq!(
+            ka.get_splitted(),
+            Some(SyllableSplit {
+                consonant: Some(Consonant::K),
+                vowel: Some(Vowel::A),
+            })
+        );
+
+        let ga = ka.to_dakuten();
+        assert_eq!(ga, Syllable::from_char('ガ'));
+        assert_eq!(ga.get_splitted().unwrap().consonant(), Some(Consonant::G));
+        assert_eq!(Syllable::from_char('ン').to_romaji_char().unwrap(), "n");
+        assert_eq!(Syllable::from_char('ヲ').to_romaji_char().unwrap(), "wo");
+        assert_eq!(
+            Syllable::from_char('ア').to_dakuten(),
+            Syllable::from_char('ア')
+        );
+    }
 
     #[test]
     pub fn test_split() {
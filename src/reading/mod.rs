@@ -4,6 +4,11 @@ pub mod traits;
 pub use r_ref::ReadingRef;
 
 use self::traits::AsReadingRef;
+use crate::{
+    trait_ext::{to_seion, LongVowelFold},
+    JapaneseExt,
+};
+use std::{fmt::Display, str::FromStr};
 
 #[cfg(feature = "furigana")]
 use crate::furi::segment::kanji::as_kanji::AsKanjiSegment;
@@ -15,7 +20,7 @@ use crate::furi::{segment::AsSegment, segment::Segment, seq::FuriSequence};
 /// Represents a Japanese 'reading' which always consists of a kana reading and sometimes an
 /// equivalent way to write that word with kanji. This is an owned variant. For a borrowed variant
 /// see [`ReadingRef`]
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
 #[cfg_attr(feature = "with_serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Reading {
     kana: String,
@@ -56,6 +61,155 @@ impl Reading {
         self.kanji.as_deref().unwrap_or(&self.kana)
     }
 
+    /// Returns the main reading which is the kanji reading if available and the kana reading
+    /// otherwise. This is an alias for `kanji_or_kana` matching `AsSegment::main_reading`.
+    #[inline]
+    pub fn main_reading(&self) -> &str {
+        self.kanji_or_kana()
+    }
+
+    /// Returns a new reading with leading and trailing whitespace, including ideographic spaces,
+    /// removed from both the kana and kanji reading. A kanji reading that is empty after trimming
+    /// is removed.
+    pub fn trim(&self) -> Reading {
+        let kana = self.kana.trim().to_string();
+        let kanji = self
+            .kanji
+            .as_deref()
+            .map(str::trim)
+            .filter(|i| !i.is_empty())
+            .map(|i| i.to_string());
+        Self::new_raw(kana, kanji)
+    }
+
+    /// Converts the kana reading to romaji using [`crate::hiragana::to_romaji`]. Returns `None` if
+    /// the kana reading contains characters that can't be converted.
+    #[cfg(feature = "hiragana")]
+    #[inline]
+    pub fn romaji(&self) -> Option<String> {
+        crate::hiragana::to_romaji(&self.kana)
+    }
+
+    /// Returns a new reading with the kana reading converted to katakana. The kanji reading is
+    /// kept as is.
+    #[inline]
+    pub fn to_katakana(&self) -> Reading {
+        Self::new_raw(self.kana.to_katakana(), self.kanji.clone())
+    }
+
+    /// Returns `true` if `typed` is the kana reading of this reading. Small kana and long vowels
+    /// are normalized before comparing so minor spelling variants like `きよう` or `きょー` are
+    /// accepted for `きょう`.
+    pub fn accepts_kana(&self, typed: &str) -> bool {
+        let normalize = |s: &str| {
+            s.fold_long_vowels(LongVowelFold::Vowel)
+                .normalize_small_kana()
+        };
+        normalize(typed) == normalize(&self.kana)
+    }
+
+    /// Encodes the reading to furigana using the given readings for each kanji literal. Adjacent
+    /// literals with a reading are grouped into a single detailed block, eg. `[音楽|おん|がく]`, and
+    /// all other characters are encoded as kana. Falls back to [`AsReadingRef::encode`] if the
+    /// given readings don't match up with the kana reading.
+    #[cfg(feature = "furigana")]
+    pub fn encode_detailed(&self, lit_readings: &[(char, &str)]) -> Furigana<String> {
+        use crate::furi::segment::{encode::FuriEncoder, kanji::KanjiRef};
+
+        let Some(kanji) = self.kanji() else {
+            return Furigana(self.kana.clone());
+        };
+
+        let mut buf = String::with_capacity(kanji.len() + self.kana.len());
+        let mut enc = FuriEncoder::new(&mut buf);
+        let mut lit_readings = lit_readings.iter().peekable();
+        let mut lits = String::new();
+        let mut readings = vec![];
+
+        for c in kanji.chars() {
+            if let Some((_, reading)) = lit_readings.next_if(|(lit, _)| *lit == c) {
+                lits.push(c);
+                readings.push(*reading);
+                continue;
+            }
+
+            if !lits.is_empty() {
+                enc.write_kanji(KanjiRef::new(&lits, &readings));
+                lits.clear();
+                readings.clear();
+            }
+            enc.write_kana(c.encode_utf8(&mut [0; 4]));
+        }
+
+        if !lits.is_empty() {
+            enc.write_kanji(KanjiRef::new(&lits, &readings));
+        }
+
+        let furi = Furigana(buf);
+        if lit_readings.next().is_some() || furi.kana_str() != self.kana {
+            return self.encode();
+        }
+        furi
+    }
+
+    /// Returns the levenshtein distance between the kana readings of `self` and `other` counted in
+    /// characters.
+    pub fn kana_levenshtein(&self, other: &Reading) -> usize {
+        let b: Vec<char> = other.kana.chars().collect();
+
+        // Only keep the previous row of the distance table.
+        let mut prev: Vec<usize> = (0..=b.len()).collect();
+        let mut cur = vec![0; b.len() + 1];
+        for (i, ac) in self.kana.chars().enumerate() {
+            cur[0] = i + 1;
+            for (j, bc) in b.iter().enumerate() {
+                let subst = prev[j] + usize::from(ac != *bc);
+                cur[j + 1] = subst.min(prev[j + 1] + 1).min(cur[j] + 1);
+            }
+            std::mem::swap(&mut prev, &mut cur);
+        }
+
+        prev[b.len()]
+    }
+
+    /// Returns `true` if the kana reading of `self` only differs from the one of `base` by the
+    /// voicing of a single non-initial kana, eg. `やまでら` compared to `やまてら`. This is the
+    /// case for readings of compounds affected by rendaku.
+    pub fn has_rendaku(&self, base: &Reading) -> bool {
+        let mut chars = self.kana.chars();
+        let mut base_chars = base.kana.chars();
+        let mut voiced = false;
+
+        for pos in 0.. {
+            let (c, base_c) = match (chars.next(), base_chars.next()) {
+                (Some(c), Some(base_c)) => (c, base_c),
+                (None, None) => break,
+                _ => return false,
+            };
+            if c == base_c {
+                continue;
+            }
+
+            // Rendaku only adds dakuten, never handakuten.
+            let is_rendaku =
+                pos > 0 && !"ぱぴぷぺぽパピプペポ".contains(c) && to_seion(c) == base_c;
+            if voiced || !is_rendaku {
+                return false;
+            }
+            voiced = true;
+        }
+
+        voiced
+    }
+
+    /// Returns `true` if both readings are written the same way, ignoring their kana readings.
+    /// Readings without kanji are compared by their kana, so `音楽（おんがく）` is not written as
+    /// `おんがく`.
+    #[inline]
+    pub fn written_as<R: AsReadingRef>(&self, other: R) -> bool {
+        self.main_reading() == other.as_reading_ref().main_reading()
+    }
+
     /// Returns the kanji reading if exists.
     #[inline]
     pub fn kanji(&self) -> Option<&str> {
@@ -68,6 +222,12 @@ impl Reading {
         &self.kana
     }
 
+    /// Returns the kana and kanji reading as `(kana, kanji)` tuple.
+    #[inline]
+    pub fn as_parts(&self) -> (&str, Option<&str>) {
+        (&self.kana, self.kanji.as_deref())
+    }
+
     /// Converts the reading to a `(String, Option<String>)` tuple where the first String is the
     /// kana reading and the `Option<String>` the kanji reading if available.
     #[inline]
@@ -90,6 +250,34 @@ impl PartialEq<ReadingRef<'_>> for Reading {
     }
 }
 
+impl Display for Reading {
+    /// Formats the reading as `kanji（kana）` or only `kana` if there is no kanji reading.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kanji() {
+            Some(kanji) => write!(f, "{kanji}（{}）", self.kana),
+            None => write!(f, "{}", self.kana),
+        }
+    }
+}
+
+impl FromStr for Reading {
+    type Err = ();
+
+    /// Parses a reading in the format `kanji（kana）` or only `kana`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some(inner) = s.strip_suffix('）') else {
+            return Ok(Reading::new(s.to_string()));
+        };
+
+        let (kanji, kana) = inner.rsplit_once('（').ok_or(())?;
+        if kanji.is_empty() {
+            return Err(());
+        }
+
+        Ok(Reading::new_with_kanji(kana.to_string(), kanji.to_string()))
+    }
+}
+
 #[cfg(feature = "furigana")]
 impl From<&FuriSequence<Segment>> for Reading {
     #[inline]
@@ -179,3 +367,213 @@ where
         Self { kana, kanji }
     }
 }
+
+/// Extracts readings from HTML ruby markup like `<ruby>音楽<rt>おんがく</rt></ruby>が`. Each base
+/// text with its `<rt>` becomes a reading with kanji and all text outside of ruby tags or without
+/// a (non empty) `<rt>` becomes a kana only reading. Other tags like `<rp>` aren't supported.
+pub fn from_ruby_html(s: &str) -> Vec<Reading> {
+    let mut out = vec![];
+    let push_kana = |out: &mut Vec<Reading>, txt: &str| {
+        if !txt.is_empty() {
+            out.push(Reading::new(txt.to_string()));
+        }
+    };
+
+    let mut rest = s;
+    while let Some(start) = rest.find("<ruby>") {
+        push_kana(&mut out, &rest[..start]);
+
+        let ruby = &rest[start + "<ruby>".len()..];
+        let Some(end) = ruby.find("</ruby>") else {
+            // Unclosed ruby tags are treated as plain text.
+            rest = &rest[start..];
+            break;
+        };
+        rest = &ruby[end + "</ruby>".len()..];
+
+        let mut inner = &ruby[..end];
+        while let Some((base, after)) = inner.split_once("<rt>") {
+            let (rt, after) = after.split_once("</rt>").unwrap_or((after, ""));
+            if rt.is_empty() {
+                push_kana(&mut out, base);
+            } else if !base.is_empty() {
+                out.push(Reading::new_with_kanji(rt.to_string(), base.to_string()));
+            }
+            inner = after;
+        }
+        push_kana(&mut out, inner);
+    }
+    push_kana(&mut out, rest);
+
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("音楽（おんがく）", Reading::new_with_kanji("おんがく".to_string(), "音楽".to_string()); "kanji")]
+    #[test_case("おんがく", Reading::new("おんがく".to_string()); "kana")]
+    #[test_case("", Reading::new(String::new()); "empty")]
+    fn test_display_from_str(s: &str, exp: Reading) {
+        let parsed = Reading::from_str(s).unwrap();
+        assert_eq!(parsed, exp);
+        assert_eq!(parsed.to_string(), s);
+    }
+
+    #[test_case("（おんがく）"; "no kanji")]
+    #[test_case("おんがく）"; "unopened")]
+    fn test_from_str_err(s: &str) {
+        assert!(Reading::from_str(s).is_err());
+    }
+
+    #[test]
+    fn test_main_reading() {
+        let reading = Reading::new_with_kanji("おんがく".to_string(), "音楽".to_string());
+        assert_eq!(reading.main_reading(), "音楽");
+        assert_eq!(reading.as_reading_ref().main_reading(), "音楽");
+
+        let reading = Reading::new("おんがく".to_string());
+        assert_eq!(reading.main_reading(), "おんがく");
+        assert_eq!(reading.as_reading_ref().main_reading(), "おんがく");
+    }
+
+    #[test_case(" おんがく ", None, "おんがく", None; "kana")]
+    #[test_case("\u{3000}おんがく", Some("音楽\u{3000}"), "おんがく", Some("音楽"); "ideographic space")]
+    #[test_case("おんがく", Some("  "), "おんがく", None; "blank kanji")]
+    fn test_trim(kana: &str, kanji: Option<&str>, exp_kana: &str, exp_kanji: Option<&str>) {
+        let reading = Reading::new_raw(kana.to_string(), kanji.map(|i| i.to_string()));
+        let trimmed = reading.trim();
+        assert_eq!(trimmed.kana(), exp_kana);
+        assert_eq!(trimmed.kanji(), exp_kanji);
+    }
+
+    #[cfg(feature = "hiragana")]
+    #[test_case("おんがく", Some("音楽"), Some("ongaku"); "with kanji")]
+    #[test_case("がっこう", None, Some("gakkou"); "sokuon")]
+    #[test_case("おんがく。", None, None; "invalid")]
+    fn test_romaji(kana: &str, kanji: Option<&str>, exp: Option<&str>) {
+        let reading = Reading::new_raw(kana.to_string(), kanji.map(|i| i.to_string()));
+        assert_eq!(reading.romaji().as_deref(), exp);
+    }
+
+    #[test_case("おんがく", None, "オンガク"; "kana")]
+    #[test_case("おんがく", Some("音楽"), "オンガク"; "with kanji")]
+    #[test_case("オンがく", None, "オンガク"; "mixed")]
+    fn test_to_katakana(kana: &str, kanji: Option<&str>, exp: &str) {
+        let reading = Reading::new_raw(kana.to_string(), kanji.map(|i| i.to_string()));
+        let katakana = reading.to_katakana();
+        assert_eq!(katakana.kana(), exp);
+        assert_eq!(katakana.kanji(), kanji);
+    }
+
+    #[test_case("きょう", true; "exact")]
+    #[test_case("きよう", true; "small kana")]
+    #[test_case("きょー", true; "long vowel mark")]
+    #[test_case("きょお", true; "long vowel spelling")]
+    #[test_case("きょく", false; "different")]
+    #[test_case("きょ", false; "missing lengthening")]
+    #[test_case("", false; "empty")]
+    fn test_accepts_kana(typed: &str, exp: bool) {
+        let reading = Reading::new_with_kanji("きょう".to_string(), "今日".to_string());
+        assert_eq!(reading.accepts_kana(typed), exp);
+    }
+
+    #[cfg(feature = "furigana")]
+    #[test]
+    fn test_encode_detailed() {
+        let reading = Reading::new_with_kanji("おんがく".to_string(), "音楽".to_string());
+        let furi = reading.encode_detailed(&[('音', "おん"), ('楽', "がく")]);
+        assert_eq!(furi, Furigana("[音楽|おん|がく]"));
+
+        let reading =
+            Reading::new_with_kanji("おんがくがすき".to_string(), "音楽が好き".to_string());
+        let furi = reading.encode_detailed(&[('音', "おん"), ('楽', "がく"), ('好', "す")]);
+        assert_eq!(furi, Furigana("[音楽|おん|がく]が[好|す]き"));
+
+        // Readings not matching the kana reading.
+        let furi = reading.encode_detailed(&[('音', "おん"), ('楽', "がく")]);
+        assert_eq!(furi, Furigana("[音楽が好き|おんがくがすき]"));
+        let furi = reading.encode_detailed(&[('音', "おん"), ('楽', "らく"), ('好', "す")]);
+        assert_eq!(furi, Furigana("[音楽が好き|おんがくがすき]"));
+
+        let reading = Reading::new("おんがく".to_string());
+        assert_eq!(reading.encode_detailed(&[]), Furigana("おんがく"));
+    }
+
+    #[test_case("おんがく", "おんがく", 0; "equal")]
+    #[test_case("おんがく", "おんかく", 1; "substitution")]
+    #[test_case("きょう", "きよう", 1; "small kana")]
+    #[test_case("とうきょう", "ときょ", 2; "deletions")]
+    #[test_case("", "かな", 2; "empty")]
+    #[test_case("かき", "きか", 2; "swapped")]
+    fn test_kana_levenshtein(a: &str, b: &str, exp: usize) {
+        let a = Reading::new(a.to_string());
+        let b = Reading::new_with_kanji(b.to_string(), "漢字".to_string());
+        assert_eq!(a.kana_levenshtein(&b), exp);
+        assert_eq!(b.kana_levenshtein(&a), exp);
+    }
+
+    #[test]
+    fn test_as_parts() {
+        let reading = Reading::new_with_kanji("おんがく".to_string(), "音楽".to_string());
+        assert_eq!(reading.as_parts(), ("おんがく", Some("音楽")));
+
+        let reading = Reading::new("おんがく".to_string());
+        assert_eq!(reading.as_parts(), ("おんがく", None));
+    }
+
+    #[test_case("やまでら", "やまてら", true; "te to de")]
+    #[test_case("てがみ", "てかみ", true; "ka to ga")]
+    #[test_case("やまてら", "やまてら", false; "equal")]
+    #[test_case("でら", "てら", false; "initial")]
+    #[test_case("やまでら", "やまてや", false; "other difference")]
+    #[test_case("いっぱい", "いっはい", false; "handakuten")]
+    #[test_case("がが", "かか", false; "multiple")]
+    #[test_case("やまでら", "やまてらく", false; "different length")]
+    #[test_case("やまてら", "やまでら", false; "unvoiced")]
+    fn test_has_rendaku(kana: &str, base: &str, exp: bool) {
+        let reading = Reading::new(kana.to_string());
+        let base = Reading::new(base.to_string());
+        assert_eq!(reading.has_rendaku(&base), exp);
+    }
+
+    #[test]
+    fn test_from_ruby_html() {
+        let html = "<ruby>音楽<rt>おんがく</rt></ruby>が<ruby>好<rt>す</rt>き</ruby>です<ruby>A<rt></rt></ruby>";
+        assert_eq!(
+            from_ruby_html(html),
+            vec![
+                Reading::new_with_kanji("おんがく".to_string(), "音楽".to_string()),
+                Reading::new("が".to_string()),
+                Reading::new_with_kanji("す".to_string(), "好".to_string()),
+                Reading::new("き".to_string()),
+                Reading::new("です".to_string()),
+                Reading::new("A".to_string()),
+            ]
+        );
+    }
+
+    #[test_case("", &[]; "empty")]
+    #[test_case("かな", &["かな"]; "plain")]
+    #[test_case("が<ruby>音楽", &["が", "<ruby>音楽"]; "unclosed")]
+    fn test_from_ruby_html_plain(html: &str, exp: &[&str]) {
+        let exp: Vec<_> = exp.iter().map(|i| Reading::new(i.to_string())).collect();
+        assert_eq!(from_ruby_html(html), exp);
+    }
+
+    #[test]
+    fn test_written_as() {
+        let kaki = Reading::new_with_kanji("かき".to_string(), "柿".to_string());
+        let kaki2 = Reading::new_with_kanji("かき".to_string(), "牡蠣".to_string());
+        assert!(!kaki.written_as(&kaki2));
+        assert!(kaki.written_as(("かき", "柿")));
+        assert!(kaki.written_as(("がき", "柿")));
+
+        let kana = Reading::new("かき".to_string());
+        assert!(!kaki.written_as(&kana));
+        assert!(kana.written_as("かき"));
+        assert!(!kana.written_as(("かき", "柿")));
+    }
+}
@@ -48,6 +48,13 @@ impl<'a> ReadingRef<'a> {
         self.kana
     }
 
+    /// Returns the main reading which is the kanji reading if available and the kana reading
+    /// otherwise.
+    #[inline]
+    pub fn main_reading(&self) -> &str {
+        self.kanji.unwrap_or(self.kana)
+    }
+
     /// Encodes the reading to furigana.
     #[cfg(feature = "furigana")]
     pub fn encode(&self) -> Furigana<String> {
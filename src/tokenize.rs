@@ -1,17 +1,36 @@
 use crate::{alphabet::Alphabet, JapaneseExt};
 use std::iter;
 
+/// The katakana middle dot.
+const NAKAGURO: char = '・';
+
 /// Returns an iterator over all kanji / kana. If `kana_same` is `true` hiragana won't be split
-/// from katakana
+/// from katakana. A single katakana middle dot `・` (U+30FB) between two katakana is kept in the
+/// katakana run, so names like `マリー・アントワネット` are a single token. See [`by_alphabet_with`].
+#[inline]
 pub fn by_alphabet(kanji: &str, kana_same: bool) -> impl Iterator<Item = &str> {
+    by_alphabet_with(kanji, kana_same, true)
+}
+
+/// Like [`by_alphabet`] but allows to configure whether a single katakana middle dot `・` between
+/// two katakana is kept as part of the katakana run. Other middle dots, eg. leading ones or
+/// multiple in a row, as well as all middle dots if `keep_nakaguro` is `false` are treated as
+/// symbols.
+pub fn by_alphabet_with(
+    kanji: &str,
+    kana_same: bool,
+    keep_nakaguro: bool,
+) -> impl Iterator<Item = &str> {
     let mut kanji_indices = kanji.char_indices().peekable();
 
     iter::from_fn(move || {
         let (curr_c_pos, curr_char) = kanji_indices.next()?;
+        let curr_alphabet = alphabet_at(kanji, curr_c_pos, curr_char, keep_nakaguro);
 
         while let Some((pos, c)) = kanji_indices.peek() {
-            if (!kana_same && curr_char.get_alphabet() != c.get_alphabet())
-                || (kana_same && !curr_char.get_alphabet().eq_both_kana(&c.get_alphabet()))
+            let alphabet = alphabet_at(kanji, *pos, *c, keep_nakaguro);
+            if (!kana_same && curr_alphabet != alphabet)
+                || (kana_same && !curr_alphabet.eq_both_kana(&alphabet))
             {
                 return Some(&kanji[curr_c_pos..*pos]);
             }
@@ -23,6 +42,60 @@ pub fn by_alphabet(kanji: &str, kana_same: bool) -> impl Iterator<Item = &str> {
     })
 }
 
+/// Returns the alphabet of the char `c` located at `pos` in `s`. The katakana middle dot is only
+/// treated as katakana if `keep_nakaguro` is `true` and it is surrounded by other katakana.
+fn alphabet_at(s: &str, pos: usize, c: char, keep_nakaguro: bool) -> Alphabet {
+    if c != NAKAGURO {
+        return c.get_alphabet();
+    }
+
+    let is_katakana = |c: Option<char>| c.is_some_and(|c| c != NAKAGURO && c.is_katakana());
+    let prev = s[..pos].chars().next_back();
+    let next = s[pos + c.len_utf8()..].chars().next();
+    if keep_nakaguro && is_katakana(prev) && is_katakana(next) {
+        Alphabet::katakana()
+    } else {
+        Alphabet::Symbol
+    }
+}
+
+/// Returns an iterator over all words of `inp`. This works like [`by_alphabet`] but keeps a kanji
+/// run together with the hiragana directly following it, so words like `食べる` stay a single token.
+/// A particle character (see [`JapaneseExt::is_particle`]) is split off only if it is the sole
+/// hiragana following the kanji, so `漢字とカタカナ` is split into `漢字`, `と` and `カタカナ`
+/// while `静かに` and `書かない` stay together. This is only a heuristic.
+pub fn words(inp: &str) -> impl Iterator<Item = &str> {
+    let mut char_indices = inp.char_indices().peekable();
+
+    iter::from_fn(move || {
+        let (start, first) = char_indices.next()?;
+        let alphabet = first.get_alphabet();
+
+        while char_indices
+            .next_if(|(_, c)| c.get_alphabet() == alphabet)
+            .is_some()
+        {}
+
+        if alphabet.is_kanji() {
+            // Okurigana. A single particle character between the kanji and the next
+            // non-hiragana character is kept as its own word.
+            let mut okurigana = char_indices.clone();
+            let mut count = 0;
+            let mut last = None;
+            while let Some((_, c)) = okurigana.next_if(|(_, c)| c.is_hiragana()) {
+                count += 1;
+                last = Some(c);
+            }
+            if count != 1 || !last.is_some_and(|c| c.is_particle()) {
+                char_indices = okurigana;
+            }
+        }
+
+        let end = char_indices.peek().map(|i| i.0).unwrap_or(inp.len());
+        Some(&inp[start..end])
+    })
+}
+
 /// Returns an iterator over all substrings of `inp` that have the given alphabet
 pub fn words_with_alphabet(inp: &str, alphabet: Alphabet) -> impl Iterator<Item = &str> {
     let inp = inp.trim();
@@ -59,6 +132,47 @@ pub fn words_with_alphabet(inp: &str, alphabet: Alphabet) -> impl Iterator<Item
     })
 }
 
+/// Fractions of the chars of a string written in each script.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct ScriptRatios {
+    pub kanji: f32,
+    pub hiragana: f32,
+    pub katakana: f32,
+    pub other: f32,
+}
+
+/// Returns the fraction of chars in `s` written in kanji, hiragana, katakana and any other script.
+/// The fractions sum up to 1.0 unless `s` is empty in which case all fractions are 0.0.
+pub fn script_ratios(s: &str) -> ScriptRatios {
+    let mut counts = [0usize; 4];
+    for c in s.chars() {
+        let alphabet = c.get_alphabet();
+        let idx = if alphabet.is_kanji() {
+            0
+        } else if alphabet.is_hiragana() {
+            1
+        } else if alphabet.is_katakana() {
+            2
+        } else {
+            3
+        };
+        counts[idx] += 1;
+    }
+
+    let total: usize = counts.iter().sum();
+    if total == 0 {
+        return ScriptRatios::default();
+    }
+
+    let ratio = |count: usize| count as f32 / total as f32;
+    ScriptRatios {
+        kanji: ratio(counts[0]),
+        hiragana: ratio(counts[1]),
+        katakana: ratio(counts[2]),
+        other: ratio(counts[3]),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -67,12 +181,40 @@ mod test {
     #[test_case("これは漢字で書いたテキストです", &["これは", "漢字", "で", "書", "いたテキストです"]; "Simple")]
     #[test_case("このテキストはかなだけでかいた", &["このテキストはかなだけでかいた"]; "Kana only")]
     #[test_case("朝に道を聞かば、夕べに死すとも可なり", &["朝", "に", "道", "を", "聞", "かば","、", "夕", "べに", "死", "すとも", "可", "なり"]; "Special char")]
+    #[test_case("マリー・アントワネットは", &["マリー・アントワネットは"]; "Nakaguro")]
     fn test_by_alphabet(inp: &str, exp: &[&str]) {
         let pairs: Vec<_> = by_alphabet(inp, true).collect();
         let exp: Vec<_> = exp.iter().map(|i| i.to_string()).collect();
         assert_eq!(pairs, exp);
     }
 
+    #[test_case("マリー・アントワネット", true, &["マリー・アントワネット"]; "kept")]
+    #[test_case("マリー・アントワネット", false, &["マリー", "・", "アントワネット"]; "not kept")]
+    #[test_case("・マリー", true, &["・", "マリー"]; "leading")]
+    #[test_case("マリー・", true, &["マリー", "・"]; "trailing")]
+    #[test_case("マリー・・アン", true, &["マリー", "・・", "アン"]; "multiple")]
+    #[test_case("は・は", true, &["は", "・", "は"]; "hiragana")]
+    #[test_case("・", true, &["・"]; "standalone")]
+    fn test_by_alphabet_nakaguro(inp: &str, keep: bool, exp: &[&str]) {
+        let tokens: Vec<_> = by_alphabet_with(inp, false, keep).collect();
+        assert_eq!(tokens, exp);
+    }
+
+    #[test_case("マリー・アントワネットは", &["マリー・アントワネット", "は"]; "nakaguro")]
+    #[test_case("食べる", &["食べる"]; "okurigana")]
+    #[test_case("漢字とカタカナ", &["漢字", "と", "カタカナ"]; "katakana")]
+    #[test_case("音楽が好きです", &["音楽", "が", "好きです"]; "particles")]
+    #[test_case("静かに", &["静かに"]; "trailing particle")]
+    #[test_case("読んでいる", &["読んでいる"]; "particle inside okurigana")]
+    #[test_case("書かない", &["書かない"]; "leading particle okurigana")]
+    #[test_case("カタカナで書いた", &["カタカナ", "で", "書いた"]; "kana first")]
+    #[test_case("見て、ください。", &["見て", "、", "ください", "。"]; "symbols")]
+    #[test_case("", &[]; "empty")]
+    fn test_words(inp: &str, exp: &[&str]) {
+        let words: Vec<&str> = words(inp).collect();
+        assert_eq!(words, exp);
+    }
+
     #[test_case("朝に道を聞かば、夕べに死すとも可なり", Alphabet::Kanji, &["朝", "道", "聞", "夕", "死", "可"]; "Kanji")]
     #[test_case("朝に道を聞かば、夕べに死すとも可なり", Alphabet::kana(), &["に", "を", "かば", "べに", "すとも", "なり"]; "Hiragana")]
     #[test_case("", Alphabet::kana(), &[]; "empty")]
@@ -80,4 +222,21 @@ mod test {
         let collected: Vec<&str> = words_with_alphabet(inp, alphabet).collect();
         assert_eq!(collected, exp);
     }
+
+    #[test]
+    fn test_script_ratios() {
+        let ratios = script_ratios("漢字とカタカナ!");
+        assert_eq!(
+            ratios,
+            ScriptRatios {
+                kanji: 0.25,
+                hiragana: 0.125,
+                katakana: 0.5,
+                other: 0.125,
+            }
+        );
+
+        assert_eq!(script_ratios("").hiragana, 0.0);
+        assert_eq!(script_ratios("かな").hiragana, 1.0);
+    }
 }
@@ -1,10 +1,10 @@
 use crate::{
     alphabet::Alphabet,
-    constants::{NORMAL_ALPHANUMERIC, WIDE_ALPHANUMERIC},
+    constants::{HALFWIDTH_KATAKANA, NORMAL_ALPHANUMERIC, WIDE_ALPHANUMERIC},
     counter,
     radicals::RADICALS,
 };
-use std::ops::Range;
+use std::{collections::HashSet, ops::Range};
 
 pub trait JapaneseExt {
     /// Returns true if self is of the given alphabet
@@ -19,12 +19,27 @@ pub trait JapaneseExt {
     /// Returns true if self is entirely written in kana
     fn is_kana(&self) -> bool;
 
-    /// Returns true if inp is entirely written with kanji
+    /// Returns true if self is a well-formed kana reading, which means it is non empty and entirely
+    /// written in hiragana and katakana including small kana and `ー` [おん]
+    fn is_valid_reading(&self) -> bool;
+
+    /// Returns true if inp is entirely written with kanji. Note that fullwidth digits [２] and the
+    /// iteration mark [々] are considered kanji as well. Use `is_ideograph` to exclude digits.
     fn is_kanji(&self) -> bool;
 
+    /// Returns true if inp is entirely written with kanji excluding fullwidth digits
+    fn is_ideograph(&self) -> bool;
+
     /// Returns true if inp has at least one kanji
     fn has_kanji(&self) -> bool;
 
+    /// Returns `true` if self contains kanji and should therefore be displayed with furigana
+    fn needs_furigana(&self) -> bool;
+
+    /// Returns `true` if self contains kanji which are not known according to `is_known`. Can be
+    /// used to only display furigana for kanji a reader doesn't know yet.
+    fn needs_furigana_with<F: Fn(char) -> bool>(&self, is_known: F) -> bool;
+
     /// Returns true if inp is build with kanji and kana only
     fn is_japanese(&self) -> bool;
 
@@ -43,15 +58,35 @@ pub trait JapaneseExt {
     /// Returns true if self has at least one hiragana letter
     fn has_hiragana(&self) -> bool;
 
+    /// Returns true if self is written in hiragana and the prolonged sound mark `ー` [らーめん]
+    fn is_hiragana_extended(&self) -> bool;
+
+    /// Returns true if self is written in katakana and the prolonged sound mark `ー` [ラーメン].
+    /// As `ー` is part of the katakana block this is equal to `is_katakana`.
+    fn is_katakana_extended(&self) -> bool;
+
     /// Returns the amount of kanji self has
     fn kanji_count(&self) -> usize;
 
+    /// Returns the amount of kanji self has excluding fullwidth digits
+    fn ideograph_count(&self) -> usize;
+
+    /// Returns the amount of distinct kanji self contains
+    fn distinct_kanji_count(&self) -> usize;
+
     /// Returns true if self is a (cjk) symbol
     fn is_symbol(&self) -> bool;
 
     /// Returns true if self is a (cjk) symbol
     fn has_symbol(&self) -> bool;
 
+    /// Returns true if self is an emoji or pictograph [😀, ☀]
+    fn is_emoji(&self) -> bool;
+
+    /// Returns true if self is japanese punctuation. Unlike [`JapaneseExt::is_symbol`] this
+    /// doesn't include other symbols like greek letters or geometric shapes [、。「」]
+    fn is_japanese_punctuation(&self) -> bool;
+
     /// Returns true if self has a roman letter
     fn has_roman_letter(&self) -> bool;
 
@@ -79,14 +114,69 @@ pub trait JapaneseExt {
     /// Returns `true` if self starts with a character of a given alphabet
     fn starts_with_alphabet(&self, a: Alphabet) -> bool;
 
+    /// Returns `true` if self starts with a small kana letter [ゃく]
+    fn starts_with_small_kana(&self) -> bool;
+
+    /// Returns `true` if self ends with a small kana letter [きゃ]
+    fn ends_with_small_kana(&self) -> bool;
+
     /// Convert Wide-alphanumeric into normal ASCII  [Ａ -> A]
     fn to_halfwidth(&self) -> String;
 
+    /// Convert halfwidth katakana into fullwidth katakana. Halfwidth (han)dakuten are combined
+    /// with the previous kana if possible [ｶﾞｯｺｳ -> ガッコウ]
+    fn to_fullwidth_kana(&self) -> String;
+
+    /// Combines kana followed by a combining (han)dakuten (U+3099, U+309A) into their precomposed
+    /// form [か + ◌゙ -> が]
+    fn compose_kana(&self) -> String;
+
+    /// Convert katakana into hiragana [オン -> おん]
+    fn to_hiragana(&self) -> String;
+
+    /// Convert hiragana into katakana [おん -> オン]
+    fn to_katakana(&self) -> String;
+
     /// Convert normal ASCII into Wide-alphanumeric [ A -> Ａ]
     fn to_fullwidth(&self) -> String;
 
+    /// Returns `true` if self is written in fullwidth forms (U+FF01-FF60, U+FFE0-FFE6) or is an
+    /// ideographic space [Ａ]
+    fn is_fullwidth(&self) -> bool;
+
+    /// Returns `true` if self is written in halfwidth forms which are printable ASCII, halfwidth
+    /// katakana and halfwidth symbols (U+FF61-FF9F, U+FFE8-FFEE) [ｱ]
+    fn is_halfwidth(&self) -> bool;
+
     /// Returns the real length of the string. This is the amount of characters
     fn real_len(&self) -> usize;
+
+    /// Converts small kana into their full size equivalents [ゃ -> や]
+    fn normalize_small_kana(&self) -> String;
+
+    /// Folds long vowels (repeated vowels and `ー`) into a canonical form so different spellings
+    /// of the same word can be compared. Eg. `とうきょう` and `とーきょー` fold to the same value.
+    fn fold_long_vowels(&self, fold: LongVowelFold) -> String;
+
+    /// Returns `true` if self equals `other` when ignoring dakuten and handakuten [が == か, ぱ == は]
+    fn eq_ignore_dakuten(&self, other: &str) -> bool;
+
+    /// Returns `true` if self equals `other` when ignoring the width of alphanumeric characters
+    /// [ＡＢＣ == ABC]
+    fn eq_width_insensitive(&self, other: &str) -> bool;
+}
+
+/// Strategy used by [`JapaneseExt::fold_long_vowels`] to fold long vowels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LongVowelFold {
+    /// Removes the lengthening entirely [とうきょう -> ときょ]
+    Drop,
+
+    /// Replaces the lengthening with the vowel of the previous kana [とーきょー -> とおきょお]
+    Vowel,
+
+    /// Replaces the lengthening with a prolonged sound mark [とうきょう -> とーきょー]
+    Choonpu,
 }
 
 impl JapaneseExt for char {
@@ -105,6 +195,8 @@ impl JapaneseExt for char {
             Alphabet::hiragana()
         } else if self.is_kanji() || self.is_roman_letter() {
             Alphabet::Kanji
+        } else if self.is_emoji() {
+            Alphabet::Emoji
         } else {
             Alphabet::Other
         }
@@ -120,6 +212,11 @@ impl JapaneseExt for char {
         self.is_hiragana() || self.is_katakana()
     }
 
+    #[inline]
+    fn is_valid_reading(&self) -> bool {
+        self.is_kana()
+    }
+
     #[inline]
     fn is_kanji(&self) -> bool {
         ((*self) >= '\u{3400}' && (*self) <= '\u{4DBF}')
@@ -131,11 +228,26 @@ impl JapaneseExt for char {
             || (*self) == '\u{3005}'
     }
 
+    #[inline]
+    fn is_ideograph(&self) -> bool {
+        self.is_kanji() && !('\u{FF10}'..='\u{FF19}').contains(self)
+    }
+
     #[inline]
     fn has_kanji(&self) -> bool {
         self.is_kanji()
     }
 
+    #[inline]
+    fn needs_furigana(&self) -> bool {
+        self.is_kanji()
+    }
+
+    #[inline]
+    fn needs_furigana_with<F: Fn(char) -> bool>(&self, is_known: F) -> bool {
+        self.is_kanji() && !is_known(*self)
+    }
+
     #[inline]
     fn is_japanese(&self) -> bool {
         self.is_kana() || self.is_kanji() || self.is_symbol() || self.is_roman_letter()
@@ -166,6 +278,16 @@ impl JapaneseExt for char {
         self.is_hiragana()
     }
 
+    #[inline]
+    fn is_hiragana_extended(&self) -> bool {
+        self.is_hiragana() || *self == 'ー'
+    }
+
+    #[inline]
+    fn is_katakana_extended(&self) -> bool {
+        self.is_katakana() || *self == 'ー'
+    }
+
     #[inline]
     fn kanji_count(&self) -> usize {
         if self.is_kanji() {
@@ -175,6 +297,16 @@ impl JapaneseExt for char {
         }
     }
 
+    #[inline]
+    fn ideograph_count(&self) -> usize {
+        usize::from(self.is_ideograph())
+    }
+
+    #[inline]
+    fn distinct_kanji_count(&self) -> usize {
+        self.kanji_count()
+    }
+
     #[inline]
     fn is_symbol(&self) -> bool {
         // https://www.htmlsymbols.xyz/ascii-symbols/fullwidth-ascii-variants
@@ -194,6 +326,48 @@ impl JapaneseExt for char {
         self.is_symbol()
     }
 
+    #[inline]
+    fn is_emoji(&self) -> bool {
+        ((*self) >= '\u{1F000}' && (*self) <= '\u{1FAFF}')
+            || ((*self) >= '\u{2600}' && (*self) <= '\u{27BF}')
+    }
+
+    #[inline]
+    fn is_japanese_punctuation(&self) -> bool {
+        matches!(
+            *self,
+            '、' | '。'
+                | '，'
+                | '．'
+                | '・'
+                | 'ー'
+                | '〜'
+                | '～'
+                | '！'
+                | '？'
+                | '：'
+                | '；'
+                | '…'
+                | '‥'
+                | '「'
+                | '」'
+                | '『'
+                | '』'
+                | '（'
+                | '）'
+                | '【'
+                | '】'
+                | '〈'
+                | '〉'
+                | '《'
+                | '》'
+                | '〔'
+                | '〕'
+                | '〝'
+                | '〟'
+        )
+    }
+
     #[inline]
     fn has_roman_letter(&self) -> bool {
         self.is_roman_letter()
@@ -260,6 +434,16 @@ impl JapaneseExt for char {
         self.is_in_alphabet(a)
     }
 
+    #[inline]
+    fn starts_with_small_kana(&self) -> bool {
+        self.is_small_kana()
+    }
+
+    #[inline]
+    fn ends_with_small_kana(&self) -> bool {
+        self.is_small_kana()
+    }
+
     #[inline]
     fn to_halfwidth(&self) -> String {
         map_char(*self, WIDE_ALPHANUMERIC, |x| x - 0xfee0).to_string()
@@ -270,10 +454,66 @@ impl JapaneseExt for char {
         map_char(*self, NORMAL_ALPHANUMERIC, |x| x + 0xfee0).to_string()
     }
 
+    #[inline]
+    fn to_fullwidth_kana(&self) -> String {
+        to_fullwidth_kana_char(*self).to_string()
+    }
+
+    #[inline]
+    fn compose_kana(&self) -> String {
+        self.to_string()
+    }
+
+    #[inline]
+    fn to_hiragana(&self) -> String {
+        to_hiragana_char(*self).to_string()
+    }
+
+    #[inline]
+    fn to_katakana(&self) -> String {
+        to_katakana_char(*self).to_string()
+    }
+
+    #[inline]
+    fn is_fullwidth(&self) -> bool {
+        ((*self) >= '\u{FF01}' && (*self) <= '\u{FF60}')
+            || ((*self) >= '\u{FFE0}' && (*self) <= '\u{FFE6}')
+            || (*self) == '\u{3000}'
+    }
+
+    #[inline]
+    fn is_halfwidth(&self) -> bool {
+        ((*self) >= '\u{0020}' && (*self) <= '\u{007E}')
+            || ((*self) >= '\u{FF61}' && (*self) <= '\u{FF9F}')
+            || ((*self) >= '\u{FFE8}' && (*self) <= '\u{FFEE}')
+    }
+
     #[inline]
     fn real_len(&self) -> usize {
         1
     }
+
+    #[inline]
+    fn normalize_small_kana(&self) -> String {
+        to_full_size_kana(*self).to_string()
+    }
+
+    #[inline]
+    fn fold_long_vowels(&self, _fold: LongVowelFold) -> String {
+        // A single character can't lengthen a previous one.
+        self.to_string()
+    }
+
+    #[inline]
+    fn eq_ignore_dakuten(&self, other: &str) -> bool {
+        let mut other = other.chars();
+        other.next().map(to_seion) == Some(to_seion(*self)) && other.next().is_none()
+    }
+
+    #[inline]
+    fn eq_width_insensitive(&self, other: &str) -> bool {
+        self.to_halfwidth() == other.to_halfwidth()
+    }
 }
 
 impl JapaneseExt for str {
@@ -292,6 +532,8 @@ impl JapaneseExt for str {
             Alphabet::hiragana()
         } else if self.is_katakana() {
             Alphabet::katakana()
+        } else if self.is_emoji() {
+            Alphabet::Emoji
         } else {
             Alphabet::Other
         }
@@ -307,16 +549,36 @@ impl JapaneseExt for str {
         self.chars().all(|s| s.is_kana())
     }
 
+    #[inline]
+    fn is_valid_reading(&self) -> bool {
+        !self.is_empty() && self.is_kana()
+    }
+
     #[inline]
     fn is_kanji(&self) -> bool {
         self.chars().all(|s| s.is_kanji())
     }
 
+    #[inline]
+    fn is_ideograph(&self) -> bool {
+        self.chars().all(|s| s.is_ideograph())
+    }
+
     #[inline]
     fn has_kanji(&self) -> bool {
         self.chars().any(|s| s.is_kanji())
     }
 
+    #[inline]
+    fn needs_furigana(&self) -> bool {
+        self.has_kanji()
+    }
+
+    #[inline]
+    fn needs_furigana_with<F: Fn(char) -> bool>(&self, is_known: F) -> bool {
+        self.chars().any(|s| s.is_kanji() && !is_known(s))
+    }
+
     #[inline]
     fn is_japanese(&self) -> bool {
         self.chars()
@@ -349,11 +611,34 @@ impl JapaneseExt for str {
         self.chars().any(|s| s.is_hiragana())
     }
 
+    #[inline]
+    fn is_hiragana_extended(&self) -> bool {
+        self.chars().all(|s| s.is_hiragana_extended())
+    }
+
+    #[inline]
+    fn is_katakana_extended(&self) -> bool {
+        self.chars().all(|s| s.is_katakana_extended())
+    }
+
     #[inline]
     fn kanji_count(&self) -> usize {
         self.chars().filter(|i| i.is_kanji()).count()
     }
 
+    #[inline]
+    fn ideograph_count(&self) -> usize {
+        self.chars().filter(|i| i.is_ideograph()).count()
+    }
+
+    #[inline]
+    fn distinct_kanji_count(&self) -> usize {
+        self.chars()
+            .filter(|i| i.is_kanji())
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
     #[inline]
     fn is_symbol(&self) -> bool {
         self.chars().all(|s| s.is_symbol())
@@ -364,6 +649,19 @@ impl JapaneseExt for str {
         self.chars().any(|s| s.is_symbol())
     }
 
+    fn is_emoji(&self) -> bool {
+        // Variation selectors and zero width joiners are part of emoji sequences like ❤️.
+        self.chars().any(|c| c.is_emoji())
+            && self
+                .chars()
+                .all(|c| c.is_emoji() || c == '\u{FE0F}' || c == '\u{200D}')
+    }
+
+    #[inline]
+    fn is_japanese_punctuation(&self) -> bool {
+        self.chars().all(|s| s.is_japanese_punctuation())
+    }
+
     #[inline]
     fn has_roman_letter(&self) -> bool {
         self.chars().any(|s| s.is_roman_letter())
@@ -408,6 +706,22 @@ impl JapaneseExt for str {
         }
     }
 
+    #[inline]
+    fn starts_with_small_kana(&self) -> bool {
+        match self.chars().next() {
+            Some(s) => s.is_small_kana(),
+            None => false,
+        }
+    }
+
+    #[inline]
+    fn ends_with_small_kana(&self) -> bool {
+        match self.chars().next_back() {
+            Some(s) => s.is_small_kana(),
+            None => false,
+        }
+    }
+
     #[inline]
     fn is_counter(&self) -> bool {
         counter::is_counter(self)
@@ -423,10 +737,224 @@ impl JapaneseExt for str {
         shift_unicode(self, NORMAL_ALPHANUMERIC, |x| x + 0xfee0)
     }
 
+    fn to_fullwidth_kana(&self) -> String {
+        let mut out = String::with_capacity(self.len());
+        let mut chars = self.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            let c = to_fullwidth_kana_char(c);
+            let voiced = chars
+                .peek()
+                .and_then(|next| add_dakuten(c, to_fullwidth_kana_char(*next)));
+
+            match voiced {
+                Some(voiced) => {
+                    out.push(voiced);
+                    chars.next();
+                }
+                None => out.push(c),
+            }
+        }
+
+        out
+    }
+
+    fn compose_kana(&self) -> String {
+        let mut out = String::with_capacity(self.len());
+        let mut chars = self.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match chars.peek().and_then(|next| compose_dakuten(c, *next)) {
+                Some(voiced) => {
+                    out.push(voiced);
+                    chars.next();
+                }
+                None => out.push(c),
+            }
+        }
+
+        out
+    }
+
+    #[inline]
+    fn to_hiragana(&self) -> String {
+        self.chars().map(to_hiragana_char).collect()
+    }
+
+    #[inline]
+    fn to_katakana(&self) -> String {
+        self.chars().map(to_katakana_char).collect()
+    }
+
+    #[inline]
+    fn is_fullwidth(&self) -> bool {
+        self.chars().all(|s| s.is_fullwidth())
+    }
+
+    #[inline]
+    fn is_halfwidth(&self) -> bool {
+        self.chars().all(|s| s.is_halfwidth())
+    }
+
     #[inline]
     fn real_len(&self) -> usize {
         self.chars().count()
     }
+
+    #[inline]
+    fn normalize_small_kana(&self) -> String {
+        self.chars().map(to_full_size_kana).collect()
+    }
+
+    fn fold_long_vowels(&self, fold: LongVowelFold) -> String {
+        let mut out = String::with_capacity(self.len());
+
+        // Vowel of the previous kana and whether it was katakana.
+        let mut prev: Option<(char, bool)> = None;
+
+        for c in self.chars() {
+            match prev {
+                Some((prev_vowel, prev_katakana)) if lengthens_vowel(prev_vowel, c) => match fold {
+                    LongVowelFold::Drop => (),
+                    LongVowelFold::Choonpu => out.push('ー'),
+                    LongVowelFold::Vowel if prev_katakana => out.push(to_katakana_char(prev_vowel)),
+                    LongVowelFold::Vowel => out.push(prev_vowel),
+                },
+                _ => {
+                    out.push(c);
+                    prev = kana_vowel(c).map(|v| (v, c.is_katakana()));
+                }
+            }
+        }
+
+        out
+    }
+
+    #[inline]
+    fn eq_ignore_dakuten(&self, other: &str) -> bool {
+        self.chars().map(to_seion).eq(other.chars().map(to_seion))
+    }
+
+    #[inline]
+    fn eq_width_insensitive(&self, other: &str) -> bool {
+        self.to_halfwidth() == other.to_halfwidth()
+    }
+}
+
+/// Removes dakuten and handakuten from a kana character [が -> か, ぱ -> は]. Other characters are
+/// returned as is.
+pub(crate) fn to_seion(c: char) -> char {
+    if c.is_katakana() {
+        return to_katakana_char(to_seion(to_hiragana_char(c)));
+    }
+
+    let n = c as u32;
+    let n = match n {
+        // か-ぢ: voiced kana are located right after their unvoiced version.
+        0x304B..=0x3062 if (n - 0x304B) % 2 == 1 => n - 1,
+        // つ-ど
+        0x3064..=0x3069 if (n - 0x3064) % 2 == 1 => n - 1,
+        // は-ぽ: each kana is followed by its dakuten and handakuten version.
+        0x306F..=0x307D => n - (n - 0x306F) % 3,
+        // ゔ
+        0x3094 => 0x3046,
+        _ => n,
+    };
+    char::from_u32(n).unwrap()
+}
+
+/// Converts a halfwidth katakana character to its fullwidth equivalent. Other characters are
+/// returned as is.
+#[inline]
+fn to_fullwidth_kana_char(c: char) -> char {
+    match (c as u32).checked_sub(0xFF61) {
+        Some(pos) => HALFWIDTH_KATAKANA.get(pos as usize).copied().unwrap_or(c),
+        None => c,
+    }
+}
+
+/// Combines the katakana `c` with the (han)dakuten `mark` [カ + ゛ -> ガ]. Returns `None` if they
+/// can't be combined.
+fn add_dakuten(c: char, mark: char) -> Option<char> {
+    let combined = match (c, mark) {
+        ('ウ', '゛') => 'ヴ',
+        ('ワ', '゛') => 'ヷ',
+        ('ヲ', '゛') => 'ヺ',
+        (_, '゛') if "カキクケコサシスセソタチツテトハヒフヘホ".contains(c) => {
+            char::from_u32(c as u32 + 1)?
+        }
+        (_, '゜') if "ハヒフヘホ".contains(c) => char::from_u32(c as u32 + 2)?,
+        _ => return None,
+    };
+    Some(combined)
+}
+
+/// Combines the kana `c` with the combining (han)dakuten `mark` [か + ◌゙ -> が]. Returns `None` if
+/// they can't be combined.
+fn compose_dakuten(c: char, mark: char) -> Option<char> {
+    let mark = match mark {
+        '\u{3099}' => '゛',
+        '\u{309A}' => '゜',
+        _ => return None,
+    };
+
+    if !c.is_hiragana() {
+        return add_dakuten(c, mark);
+    }
+
+    let voiced = to_hiragana_char(add_dakuten(to_katakana_char(c), mark)?);
+    voiced.is_hiragana().then_some(voiced)
+}
+
+/// Converts a small kana character to its full size equivalent. Other characters are returned as
+/// is.
+#[inline]
+fn to_full_size_kana(c: char) -> char {
+    if c.is_small_kana() {
+        // Full size kana are always located right after their small version.
+        char::from_u32(c as u32 + 1).unwrap()
+    } else {
+        c
+    }
+}
+
+/// Returns `true` if `c` lengthens a kana with the vowel `prev_vowel`.
+#[inline]
+fn lengthens_vowel(prev_vowel: char, c: char) -> bool {
+    let c = to_hiragana_char(c);
+    c == 'ー'
+        || c == prev_vowel
+        || (prev_vowel == 'お' && c == 'う')
+        || (prev_vowel == 'え' && c == 'い')
+}
+
+/// Returns the vowel of a kana character as hiragana (one of `あいうえお`).
+fn kana_vowel(c: char) -> Option<char> {
+    Some(match to_hiragana_char(c) {
+        'あ' | 'ぁ' | 'か' | 'が' | 'さ' | 'ざ' | 'た' | 'だ' | 'な' | 'は' | 'ば' | 'ぱ'
+        | 'ま' | 'や' | 'ゃ' | 'ら' | 'わ' | 'ゎ' => 'あ',
+        'い' | 'ぃ' | 'き' | 'ぎ' | 'し' | 'じ' | 'ち' | 'ぢ' | 'に' | 'ひ' | 'び' | 'ぴ'
+        | 'み' | 'り' | 'ゐ' => 'い',
+        'う' | 'ぅ' | 'く' | 'ぐ' | 'す' | 'ず' | 'つ' | 'づ' | 'ぬ' | 'ふ' | 'ぶ' | 'ぷ'
+        | 'む' | 'ゆ' | 'ゅ' | 'る' | 'ゔ' => 'う',
+        'え' | 'ぇ' | 'け' | 'げ' | 'せ' | 'ぜ' | 'て' | 'で' | 'ね' | 'へ' | 'べ' | 'ぺ'
+        | 'め' | 'れ' | 'ゑ' => 'え',
+        'お' | 'ぉ' | 'こ' | 'ご' | 'そ' | 'ぞ' | 'と' | 'ど' | 'の' | 'ほ' | 'ぼ' | 'ぽ'
+        | 'も' | 'よ' | 'ょ' | 'ろ' | 'を' => 'お',
+        _ => return None,
+    })
+}
+
+/// Converts a katakana character to its hiragana equivalent. Other characters are returned as is.
+#[inline]
+fn to_hiragana_char(c: char) -> char {
+    map_char(c, 0x30A1..0x30F7, |x| x - 0x60)
+}
+
+/// Converts a hiragana character to its katakana equivalent. Other characters are returned as is.
+#[inline]
+fn to_katakana_char(c: char) -> char {
+    map_char(c, 0x3041..0x3097, |x| x + 0x60)
 }
 
 fn shift_unicode<D, S: AsRef<str>>(s: S, range: Range<u32>, conv: D) -> String
@@ -464,6 +992,15 @@ mod tests {
         assert_eq!(inp.is_kanji(), expcected);
     }
 
+    #[test_case("人人", 2, 1)]
+    #[test_case("日本人", 3, 3)]
+    #[test_case("人と人", 2, 1; "with kana")]
+    #[test_case("かな", 0, 0)]
+    fn test_distinct_kanji_count(inp: &str, count: usize, distinct: usize) {
+        assert_eq!(inp.kanji_count(), count);
+        assert_eq!(inp.distinct_kanji_count(), distinct);
+    }
+
     #[test_case("、",true; "Symbol")]
     #[test_case("音",false; "Kanji")]
     #[test_case("々", false)]
@@ -472,6 +1009,16 @@ mod tests {
         assert_eq!(inp.is_symbol(), expcected);
     }
 
+    #[test_case("。", true; "period")]
+    #[test_case("「」", true; "brackets")]
+    #[test_case("ー", true; "choonpu")]
+    #[test_case("■", false; "geometric shape")]
+    #[test_case("α", false; "greek")]
+    #[test_case("あ", false; "kana")]
+    fn test_is_japanese_punctuation(inp: &str, exp: bool) {
+        assert_eq!(inp.is_japanese_punctuation(), exp);
+    }
+
     #[test_case("1234","１２３４"; "To fullwidth")]
     fn test_to_fullwidth(inp: &str, exp: &str) {
         assert_eq!(inp.to_fullwidth().as_str(), exp);
@@ -483,14 +1030,47 @@ mod tests {
         assert_eq!(inp.to_halfwidth().as_str(), exp);
     }
 
+    #[test_case("おんがく", "オンガク"; "hiragana")]
+    #[test_case("音楽が好き", "音楽ガ好キ"; "with kanji")]
+    #[test_case("ゔぁー", "ヴァー"; "small and long vowel")]
+    fn test_to_katakana(inp: &str, exp: &str) {
+        assert_eq!(inp.to_katakana(), exp);
+        assert_eq!(exp.to_hiragana(), inp);
+    }
+
+    #[test_case("Ａ", true, false; "fullwidth latin")]
+    #[test_case("ＡＢＣ１２３", true, false; "fullwidth alphanumeric")]
+    #[test_case("\u{3000}", true, false; "ideographic space")]
+    #[test_case("ｱ", false, true; "halfwidth katakana")]
+    #[test_case("ABC 123", false, true; "ascii")]
+    #[test_case("ｶﾞｯｺｳ", false, true; "halfwidth katakana with dakuten")]
+    #[test_case("Aあ", false, false; "mixed")]
+    #[test_case("音", false, false; "kanji")]
+    fn test_width(inp: &str, fullwidth: bool, halfwidth: bool) {
+        assert_eq!(inp.is_fullwidth(), fullwidth);
+        assert_eq!(inp.is_halfwidth(), halfwidth);
+    }
+
     #[test_case("音楽", Alphabet::Kanji)]
     #[test_case("、", Alphabet::Symbol)]
+    #[test_case("😀", Alphabet::Emoji; "emoji")]
+    #[test_case("❤️", Alphabet::Emoji; "emoji sequence")]
+    #[test_case("音", Alphabet::Kanji; "single kanji")]
     #[test_case("お", Alphabet::hiragana())]
     #[test_case("お", Alphabet::kana())]
     fn test_alphabet_eq(inp: &str, a: Alphabet) {
         assert_eq!(inp.get_alphabet(), a)
     }
 
+    #[test_case('😀', true; "emoji")]
+    #[test_case('☀', true; "sun")]
+    #[test_case('音', false; "kanji")]
+    #[test_case('、', false; "symbol")]
+    fn test_is_emoji(c: char, exp: bool) {
+        assert_eq!(c.is_emoji(), exp);
+        assert_eq!(c.get_alphabet().is_emoji(), exp);
+    }
+
     #[test_case("よ", false)]
     #[test_case("ょ", true)]
     #[test_case("ゃ", true)]
@@ -501,4 +1081,142 @@ mod tests {
     fn test_small_kana(inp: &str, is_small: bool) {
         assert!(inp.is_small_kana() == is_small);
     }
+
+    #[test_case("ゃく", true, false; "starts small")]
+    #[test_case("きゃ", false, true; "ends small")]
+    #[test_case("ャ", true, true; "single small katakana")]
+    #[test_case("きや", false, false; "no small")]
+    #[test_case("", false, false; "empty")]
+    fn test_small_kana_edges(inp: &str, starts: bool, ends: bool) {
+        assert_eq!(inp.starts_with_small_kana(), starts);
+        assert_eq!(inp.ends_with_small_kana(), ends);
+    }
+
+    #[test_case("きゃ", "きや")]
+    #[test_case("きゅうしょ", "きゆうしよ")]
+    #[test_case("ちぇっく", "ちえっく")]
+    #[test_case("キャ", "キヤ")]
+    #[test_case("ファイル", "フアイル")]
+    #[test_case("音楽", "音楽")]
+    fn test_normalize_small_kana(inp: &str, exp: &str) {
+        assert_eq!(inp.normalize_small_kana(), exp);
+    }
+
+    #[test_case("とうきょう", "とーきょー", LongVowelFold::Drop, "ときょ")]
+    #[test_case("とうきょう", "とーきょー", LongVowelFold::Vowel, "とおきょお")]
+    #[test_case("とうきょう", "とーきょー", LongVowelFold::Choonpu, "とーきょー")]
+    #[test_case("おかあさん", "おかーさん", LongVowelFold::Vowel, "おかあさん")]
+    #[test_case("せんせい", "せんせー", LongVowelFold::Drop, "せんせ")]
+    #[test_case("コーヒー", "コオヒイ", LongVowelFold::Vowel, "コオヒイ")]
+    fn test_fold_long_vowels(a: &str, b: &str, fold: LongVowelFold, exp: &str) {
+        assert_eq!(a.fold_long_vowels(fold), exp);
+        assert_eq!(b.fold_long_vowels(fold), exp);
+    }
+
+    #[test_case("がっこう", "かっこう", true)]
+    #[test_case("ぱん", "はん", true)]
+    #[test_case("ばん", "ぱん", true)]
+    #[test_case("どうぞ", "とうそ", true)]
+    #[test_case("ヴァイオリン", "ウァイオリン", true)]
+    #[test_case("ガッコウ", "カッコウ", true)]
+    #[test_case("ガッコウ", "かっこう", false)]
+    #[test_case("かっこう", "かっこ", false)]
+    #[test_case("ちず", "ぢす", true)]
+    fn test_eq_ignore_dakuten(a: &str, b: &str, exp: bool) {
+        assert_eq!(a.eq_ignore_dakuten(b), exp);
+        assert_eq!(b.eq_ignore_dakuten(a), exp);
+    }
+
+    #[test]
+    fn test_eq_ignore_dakuten_strict() {
+        assert_ne!("がっこう", "かっこう");
+        assert!("がっこう".eq_ignore_dakuten("かっこう"));
+        assert!('ぽ'.eq_ignore_dakuten("ほ"));
+        assert!(!'ぽ'.eq_ignore_dakuten("ほほ"));
+    }
+
+    #[test_case("ＡＢＣ", "ABC", true; "fullwidth latin")]
+    #[test_case("ＡＢＣ１２３", "ＡBC12３", true; "mixed width")]
+    #[test_case("５日", "5日", true; "with kanji")]
+    #[test_case("ＡＢＣ", "abc", false; "different case")]
+    #[test_case("ＡＢ", "ABC", false; "different length")]
+    fn test_eq_width_insensitive(a: &str, b: &str, exp: bool) {
+        assert_eq!(a.eq_width_insensitive(b), exp);
+        assert_eq!(b.eq_width_insensitive(a), exp);
+    }
+
+    #[test_case("らーめん", true, false; "hiragana with long vowel")]
+    #[test_case("ラーメン", false, true; "katakana with long vowel")]
+    #[test_case("ー", true, true; "long vowel only")]
+    #[test_case("らーメン", false, false; "mixed")]
+    fn test_kana_extended(inp: &str, hiragana: bool, katakana: bool) {
+        assert_eq!(inp.is_hiragana_extended(), hiragana);
+        assert_eq!(inp.is_katakana_extended(), katakana);
+        assert!(!inp.is_hiragana());
+    }
+
+    #[test_case("おんがく", false; "kana")]
+    #[test_case("音楽が好き", true; "kanji")]
+    #[test_case("ABC", false; "latin")]
+    fn test_needs_furigana(inp: &str, exp: bool) {
+        assert_eq!(inp.needs_furigana(), exp);
+    }
+
+    #[test_case("音楽が好き", false; "all known")]
+    #[test_case("音楽が大好き", true; "unknown kanji")]
+    #[test_case("おんがく", false; "kana")]
+    fn test_needs_furigana_with(inp: &str, exp: bool) {
+        let known: HashSet<char> = ['音', '楽', '好'].into_iter().collect();
+        assert_eq!(inp.needs_furigana_with(|c| known.contains(&c)), exp);
+        assert!('大'.needs_furigana_with(|c| known.contains(&c)));
+        assert!(!'好'.needs_furigana_with(|c| known.contains(&c)));
+    }
+
+    #[test_case("ｶﾞｯｺｳ", "ガッコウ"; "dakuten")]
+    #[test_case("ﾊﾟﾋﾟﾌﾞ", "パピブ"; "handakuten")]
+    #[test_case("ｳﾞｧｲｵﾘﾝ", "ヴァイオリン"; "vu")]
+    #[test_case("ｱﾞﾝ｡", "ア゛ン。"; "uncombinable mark")]
+    #[test_case("ﾃﾞｰﾀを｢ｺﾋﾟｰ｣", "データを「コピー」"; "mixed")]
+    #[test_case("ABC", "ABC"; "ascii")]
+    fn test_to_fullwidth_kana(inp: &str, exp: &str) {
+        assert_eq!(inp.to_fullwidth_kana(), exp);
+    }
+
+    #[test_case("か\u{3099}", "が"; "dakuten")]
+    #[test_case("は\u{309A}ン", "ぱン"; "handakuten")]
+    #[test_case("カ\u{3099}ッコウ", "ガッコウ"; "katakana")]
+    #[test_case("う\u{3099}ウ\u{3099}", "ゔヴ"; "vu")]
+    #[test_case("わ\u{3099}", "わ\u{3099}"; "no hiragana equivalent")]
+    #[test_case("あ\u{3099}が", "あ\u{3099}が"; "uncombinable")]
+    #[test_case("が", "が"; "precomposed")]
+    fn test_compose_kana(inp: &str, exp: &str) {
+        assert_eq!(inp.compose_kana(), exp);
+    }
+
+    #[test_case("２", true, false; "fullwidth digit")]
+    #[test_case("音", true, true; "kanji")]
+    #[test_case("々", true, true; "iteration mark")]
+    #[test_case("2", false, false; "ascii digit")]
+    fn test_is_ideograph(inp: &str, kanji: bool, ideograph: bool) {
+        assert_eq!(inp.is_kanji(), kanji);
+        assert_eq!(inp.is_ideograph(), ideograph);
+    }
+
+    #[test]
+    fn test_ideograph_count() {
+        assert_eq!("２０２３年".kanji_count(), 5);
+        assert_eq!("２０２３年".ideograph_count(), 1);
+        assert_eq!('２'.ideograph_count(), 0);
+    }
+
+    #[test_case("おん", true; "hiragana")]
+    #[test_case("ラーメン", true; "katakana")]
+    #[test_case("きょう", true; "small kana")]
+    #[test_case("らーめん", true; "long vowel")]
+    #[test_case("音", false; "kanji")]
+    #[test_case("おn", false; "latin")]
+    #[test_case("", false; "empty")]
+    fn test_is_valid_reading(inp: &str, exp: bool) {
+        assert_eq!(inp.is_valid_reading(), exp);
+    }
 }